@@ -0,0 +1,66 @@
+"""Benchmark of save_subplots_png on a 4x4 grid of line plots.
+
+The plots are exported by save_subplots_png, rendered in parallel,
+and then one after another with save_png, for comparison.
+"""
+
+import os
+import tempfile
+import time
+
+import plotive as pv
+import numpy as np
+
+ROWS = 4
+COLS = 4
+POINTS = 20_000
+REPEAT = 3
+
+
+def make_plot(idx, subplot=None):
+    return pv.Plot(
+        subplot=subplot,
+        series=[pv.series.Line(x="x", y=f"y{idx}")],
+        x_axis=pv.Axis(ticks="auto", grid="auto"),
+        y_axis=pv.Axis(ticks="auto", grid="auto"),
+    )
+
+
+def best_of(func):
+    best = float("inf")
+    for _ in range(REPEAT):
+        start = time.perf_counter()
+        func()
+        best = min(best, time.perf_counter() - start)
+    return best
+
+
+if __name__ == "__main__":
+    x = np.linspace(0, 20 * np.pi, POINTS)
+    data_src = {"x": x}
+    plots = []
+    for row in range(1, ROWS + 1):
+        for col in range(1, COLS + 1):
+            idx = len(plots)
+            data_src[f"y{idx}"] = np.sin(x * (idx + 1) / 4) + 0.1 * np.random.randn(POINTS)
+            plots.append(make_plot(idx, (row, col)))
+
+    fig = pv.Figure(title="4x4 line plots", plots=plots)
+    single_figs = [pv.Figure(plot=make_plot(idx)) for idx in range(len(plots))]
+
+    with tempfile.TemporaryDirectory() as out_dir:
+
+        def parallel():
+            fig.save_subplots_png(os.path.join(out_dir, "par_{}.png"), data_source=data_src)
+
+        def sequential():
+            for idx, single_fig in enumerate(single_figs):
+                path = os.path.join(out_dir, f"seq_{idx + 1}.png")
+                single_fig.save_png(path, data_source=data_src)
+
+        t_par = best_of(parallel)
+        t_seq = best_of(sequential)
+
+    print(f"{ROWS}x{COLS} plots of {POINTS} points, {os.cpu_count()} CPUs")
+    print(f"sequential: {t_seq * 1000:.0f} ms")
+    print(f"parallel:   {t_par * 1000:.0f} ms ({t_seq / t_par:.1f}x)")
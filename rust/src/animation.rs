@@ -0,0 +1,121 @@
+//! Encoding of rendered frames into animated PNG or GIF files.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    Apng,
+    Gif,
+}
+
+impl Format {
+    /// The format matching the extension of `path`: `.png`/`.apng` or `.gif`.
+    pub fn from_path(path: &str) -> Option<Format> {
+        let ext = std::path::Path::new(path).extension()?.to_str()?;
+        match ext.to_ascii_lowercase().as_str() {
+            "png" | "apng" => Some(Format::Apng),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        }
+    }
+}
+
+enum Writer {
+    Apng(png::Writer<BufWriter<File>>),
+    Gif(gif::Encoder<BufWriter<File>>),
+}
+
+/// Writes RGBA frames of identical size to an animation file, one frame at a time.
+pub struct Encoder {
+    writer: Writer,
+    width: u32,
+    height: u32,
+    /// Delay between frames in centiseconds, the unit of GIF
+    gif_delay: u16,
+}
+
+impl Encoder {
+    /// Create the animation file at `path`.
+    /// If `repeat` is false, the animation is played once.
+    pub fn create(
+        path: &str,
+        format: Format,
+        (width, height): (u32, u32),
+        frames: u32,
+        fps: f32,
+        repeat: bool,
+    ) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        let file = BufWriter::new(file);
+        let writer = match format {
+            Format::Apng => {
+                let mut encoder = png::Encoder::new(file, width, height);
+                encoder.set_color(png::ColorType::Rgba);
+                encoder.set_depth(png::BitDepth::Eight);
+                // 0 plays means looping forever
+                let plays = if repeat { 0 } else { 1 };
+                encoder
+                    .set_animated(frames, plays)
+                    .map_err(|e| e.to_string())?;
+                let delay_ms = (1000.0 / fps).round().clamp(1.0, u16::MAX as f32) as u16;
+                encoder
+                    .set_frame_delay(delay_ms, 1000)
+                    .map_err(|e| e.to_string())?;
+                Writer::Apng(encoder.write_header().map_err(|e| e.to_string())?)
+            }
+            Format::Gif => {
+                let (Ok(w), Ok(h)) = (u16::try_from(width), u16::try_from(height)) else {
+                    return Err(format!(
+                        "GIF size is limited to 65535x65535 pixels, got {}x{}",
+                        width, height
+                    ));
+                };
+                let mut encoder = gif::Encoder::new(file, w, h, &[]).map_err(|e| e.to_string())?;
+                // without the repeat extension, a GIF is played once
+                if repeat {
+                    encoder
+                        .set_repeat(gif::Repeat::Infinite)
+                        .map_err(|e| e.to_string())?;
+                }
+                Writer::Gif(encoder)
+            }
+        };
+        Ok(Encoder {
+            writer,
+            width,
+            height,
+            gif_delay: (100.0 / fps).round().clamp(1.0, u16::MAX as f32) as u16,
+        })
+    }
+
+    /// Write the next frame, given as non-premultiplied RGBA bytes.
+    pub fn write_frame(&mut self, mut rgba: Vec<u8>) -> Result<(), String> {
+        debug_assert_eq!(rgba.len(), self.width as usize * self.height as usize * 4);
+        match &mut self.writer {
+            Writer::Apng(writer) => writer.write_image_data(&rgba).map_err(|e| e.to_string()),
+            Writer::Gif(encoder) => {
+                let mut frame = gif::Frame::from_rgba_speed(
+                    self.width as u16,
+                    self.height as u16,
+                    &mut rgba,
+                    10,
+                );
+                frame.delay = self.gif_delay;
+                encoder.write_frame(&frame).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// Finalize the file.
+    pub fn finish(self) -> Result<(), String> {
+        match self.writer {
+            Writer::Apng(writer) => writer.finish().map_err(|e| e.to_string()),
+            // into_inner writes the GIF trailer
+            Writer::Gif(encoder) => {
+                let mut file = encoder.into_inner().map_err(|e| e.to_string())?;
+                file.flush().map_err(|e| e.to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Min/max decimation of long line series.
+//!
+//! At screen resolution, a line of millions of points mostly draws the same pixels over and
+//! over. Keeping only the extreme values of each group of consecutive points preserves the
+//! visual envelope of the line while cutting the number of drawn segments.
+
+/// Reduce the `x`/`y` points to about `max_points` points.
+///
+/// Points with a missing or NaN coordinate break the line in runs, which are decimated
+/// separately and joined back with a NaN point, so that the gaps are still drawn.
+/// Each run gets a share of `max_points` proportional to its length, and keeps at least
+/// its first and last points, so a line with many gaps may keep more than `max_points` points.
+/// In a run, the points in between the first and last are split in groups of consecutive
+/// points, of which the points with the minimum and maximum y value are kept,
+/// in their original order.
+/// Returns `None` if the line has no more than `max_points` points.
+///
+/// `x` and `y` must have the same length.
+pub fn min_max(
+    x: &[Option<f64>],
+    y: &[Option<f64>],
+    max_points: usize,
+) -> Option<(Vec<f64>, Vec<f64>)> {
+    debug_assert!(max_points >= 4);
+    debug_assert_eq!(x.len(), y.len());
+    if x.len() <= max_points {
+        return None;
+    }
+
+    let mut runs: Vec<Vec<(f64, f64)>> = Vec::new();
+    let mut run = Vec::new();
+    for (x, y) in x.iter().zip(y) {
+        match (x, y) {
+            (Some(x), Some(y)) if !x.is_nan() && !y.is_nan() => run.push((*x, *y)),
+            _ if !run.is_empty() => runs.push(std::mem::take(&mut run)),
+            _ => (),
+        }
+    }
+    if !run.is_empty() {
+        runs.push(run);
+    }
+
+    let n: usize = runs.iter().map(Vec::len).sum();
+    let mut res = Vec::with_capacity(max_points + runs.len());
+    for (i, run) in runs.iter().enumerate() {
+        if i > 0 {
+            res.push((f64::NAN, f64::NAN));
+        }
+        let budget = (run.len() * max_points / n).max(2);
+        min_max_run(run, budget, &mut res);
+    }
+    Some(res.into_iter().unzip())
+}
+
+/// Decimate a run of valid points to at most `budget` points, appended to `res`.
+fn min_max_run(points: &[(f64, f64)], budget: usize, res: &mut Vec<(f64, f64)>) {
+    let n = points.len();
+    if n <= budget {
+        res.extend_from_slice(points);
+        return;
+    }
+
+    let inner = n - 2;
+    let groups = (budget - 2) / 2;
+    res.push(points[0]);
+    for g in 0..groups {
+        let start = 1 + g * inner / groups;
+        let end = 1 + (g + 1) * inner / groups;
+        if start == end {
+            continue;
+        }
+        let mut min = start;
+        let mut max = start;
+        for i in start + 1..end {
+            if points[i].1 < points[min].1 {
+                min = i;
+            }
+            if points[i].1 > points[max].1 {
+                max = i;
+            }
+        }
+        let (first, second) = if min <= max { (min, max) } else { (max, min) };
+        res.push(points[first]);
+        if second != first {
+            res.push(points[second]);
+        }
+    }
+    res.push(points[n - 1]);
+}
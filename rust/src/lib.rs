@@ -1,10 +1,15 @@
 use plotive::ColorU8;
 use pyo3::prelude::*;
 
+mod animation;
+mod color;
+mod decimate;
 mod py_annot;
 mod py_data;
 mod py_des;
 mod py_style;
+mod strict;
+mod window;
 
 fn getattr_not_none<'py>(
     obj: &Bound<'py, PyAny>,
@@ -21,68 +26,503 @@ fn getattr_not_none<'py>(
     }
 }
 
+/// Attributes set on errors raised during extraction,
+/// holding the attribute path and the message without the path.
+const ERR_PATH_ATTR: &str = "_plotive_path";
+const ERR_MSG_ATTR: &str = "_plotive_msg";
+
+/// Locate extraction errors in the figure definition.
+/// Each level of the extraction prepends its own path segment, so that the final message
+/// reads e.g. `figure.plots[0].series[3]: Failed to parse color string 'bleu'`.
+trait ErrPath<T> {
+    fn with_path(self, path: impl FnOnce() -> String) -> PyResult<T>;
+}
+
+impl<T> ErrPath<T> for PyResult<T> {
+    fn with_path(self, path: impl FnOnce() -> String) -> PyResult<T> {
+        self.map_err(|err| Python::attach(|py| prepend_err_path(py, err, path())))
+    }
+}
+
+fn prepend_err_path(py: Python<'_>, err: PyErr, path: String) -> PyErr {
+    use pyo3::exceptions::{PyIndexError, PyKeyError, PyTypeError, PyValueError};
+
+    // other exceptions (e.g. raised by user code) may not be constructible from a message
+    if !(err.is_instance_of::<PyValueError>(py)
+        || err.is_instance_of::<PyTypeError>(py)
+        || err.is_instance_of::<PyKeyError>(py)
+        || err.is_instance_of::<PyIndexError>(py))
+    {
+        return err;
+    }
+
+    let value = err.value(py);
+    let inner = value
+        .getattr_opt(ERR_PATH_ATTR)
+        .ok()
+        .flatten()
+        .and_then(|p| p.extract::<String>().ok());
+    let msg = value
+        .getattr_opt(ERR_MSG_ATTR)
+        .ok()
+        .flatten()
+        .and_then(|m| m.extract::<String>().ok())
+        // KeyError formats its message with quotes, the first arg is the raw message
+        .or_else(|| value.getattr("args").ok()?.get_item(0).ok()?.extract().ok())
+        .unwrap_or_else(|| value.to_string());
+
+    let full_path = match &inner {
+        Some(inner) if inner.starts_with('[') => format!("{}{}", path, inner),
+        Some(inner) => format!("{}.{}", path, inner),
+        None => path,
+    };
+    let new_err = PyErr::from_type(err.get_type(py), format!("{}: {}", full_path, msg));
+    let new_value = new_err.value(py);
+    if new_value.setattr(ERR_PATH_ATTR, &full_path).is_err()
+        || new_value.setattr(ERR_MSG_ATTR, &msg).is_err()
+    {
+        return err;
+    }
+    // keep the traceback of the original error, not of the intermediate re-raises
+    let cause = if inner.is_some() {
+        err.cause(py)
+    } else {
+        Some(err)
+    };
+    new_err.set_cause(py, cause);
+    new_err
+}
+
 fn extract_class_name(obj: &Bound<'_, PyAny>) -> PyResult<String> {
     let class = obj.getattr("__class__")?;
     let name = class.getattr("__name__")?.str()?;
     Ok(name.to_str()?.to_owned())
 }
 
+/// Describe a received value for type errors, as its type name and a truncated repr.
+fn describe_value(obj: &Bound<'_, PyAny>) -> String {
+    const MAX_REPR_LEN: usize = 40;
+    let type_name = extract_class_name(obj).unwrap_or_else(|_| "?".to_owned());
+    let repr = obj.repr().map(|r| r.to_string()).unwrap_or_default();
+    if repr.chars().count() > MAX_REPR_LEN {
+        let repr: String = repr.chars().take(MAX_REPR_LEN).collect();
+        format!("{}: {}...", type_name, repr)
+    } else {
+        format!("{}: {}", type_name, repr)
+    }
+}
+
+/// Extract a float, also accepting numpy scalars (e.g. `numpy.float32`)
+/// and other objects implementing `__float__`.
+fn extract_f64_like(obj: &Bound<'_, PyAny>) -> PyResult<f64> {
+    if let Ok(value) = obj.extract::<f64>() {
+        return Ok(value);
+    }
+    if obj.hasattr("__float__")? {
+        return obj.call_method0("__float__")?.extract();
+    }
+    Err(pyo3::exceptions::PyTypeError::new_err(format!(
+        "Expected a number. Got {}.",
+        describe_value(obj)
+    )))
+}
+
+fn extract_f32_like(obj: &Bound<'_, PyAny>) -> PyResult<f32> {
+    extract_f64_like(obj).map(|value| value as f32)
+}
+
 fn extract_color(py_col: &Bound<'_, PyAny>) -> PyResult<ColorU8> {
     if let Ok(col) = py_col.extract::<&str>() {
-        Ok(col.parse().map_err(|e| {
-            pyo3::exceptions::PyValueError::new_err(format!(
-                "Failed to parse color string '{}': {}",
-                col, e
-            ))
-        })?)
+        col.parse().or_else(|e| {
+            color::parse_fallback(col).ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Failed to parse color string '{}': {}",
+                    col, e
+                ))
+            })
+        })
     } else if let Ok((r, g, b)) = py_col.extract::<(u8, u8, u8)>() {
         Ok(ColorU8::from_rgb(r, g, b))
     } else if let Ok((r, g, b, a)) = py_col.extract::<(u8, u8, u8, u8)>() {
         Ok(ColorU8::from_rgba(r, g, b, a))
     } else if let Ok((r, g, b, a)) = py_col.extract::<(u8, u8, u8, f32)>() {
-        if a < 0.0 || a > 1.0 {
+        if !(0.0..=1.0).contains(&a) {
             return Err(pyo3::exceptions::PyValueError::new_err(
                 "Alpha value must be between 0.0 and 1.0.",
             ));
         }
         Ok(ColorU8::from_rgba(r, g, b, (a * 255.0) as u8))
     } else {
-        Err(pyo3::exceptions::PyTypeError::new_err(
-            "Color must be a string.",
-        ))
+        Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Color must be a string. Got {}.",
+            describe_value(py_col)
+        )))
+    }
+}
+
+fn extract_optional_style(py_style: &Bound<'_, PyAny>) -> PyResult<Option<plotive::Style>> {
+    if py_style.is_none() {
+        Ok(None)
+    } else {
+        py_style::extract_style(py_style).map(Some)
     }
 }
 
+/// The parameters of the raster exports. Unset options keep the plotive defaults.
+fn raster_params(
+    style: Option<plotive::Style>,
+    scale: Option<f32>,
+) -> plotive_pxl::Params<'static> {
+    let mut params: plotive_pxl::Params = Default::default();
+    if let Some(style) = style {
+        params.style = style;
+    }
+    if let Some(scale) = scale {
+        params.scale = scale;
+    }
+    params
+}
+
+/// Render a figure to a pixmap, optionally overriding its size.
+fn render_pixmap(
+    py_fig: &Bound<'_, PyAny>,
+    data_src: &dyn plotive::data::Source,
+    size: Option<(f32, f32)>,
+    params: plotive_pxl::Params,
+) -> PyResult<tiny_skia::Pixmap> {
+    use plotive_pxl::ToPixmap;
+
+    strict::check_figure(py_fig)?;
+    let mut fig = py_des::extract_figure(py_fig, data_src)?;
+    if let Some((w, h)) = size {
+        fig = fig.with_size(plotive::geom::Size::new(w, h));
+    }
+    py_style::check_palette_cycle(py_fig, &params.style)?;
+    let pixmap = fig.to_pixmap(data_src, params).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to render figure: {}", e))
+    })?;
+    py_data::take_read_error()?;
+    Ok(pixmap)
+}
+
+fn encode_png(pixmap: &tiny_skia::Pixmap) -> PyResult<Vec<u8>> {
+    pixmap
+        .encode_png()
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("Failed to encode PNG: {}", e)))
+}
+
+/// Render a figure and save it as PNG, returning the size of the image in pixels.
+fn save_png_pixmap(
+    py_fig: &Bound<'_, PyAny>,
+    path: &str,
+    data_src: &dyn plotive::data::Source,
+    size: Option<(f32, f32)>,
+    params: plotive_pxl::Params,
+) -> PyResult<(u32, u32)> {
+    // rendered to a pixmap first, to report the actual pixel size
+    let pixmap = render_pixmap(py_fig, data_src, size, params)?;
+    let png = encode_png(&pixmap)?;
+    std::fs::write(path, png)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(format!("Failed to save PNG: {}", e)))?;
+    Ok((pixmap.width(), pixmap.height()))
+}
+/// The non-premultiplied RGBA bytes of a rendered pixmap.
+fn pixmap_rgba(pixmap: &tiny_skia::Pixmap) -> Vec<u8> {
+    // pixmap pixels are premultiplied by alpha
+    let mut rgba = Vec::with_capacity(pixmap.pixels().len() * 4);
+    for px in pixmap.pixels() {
+        let px = px.demultiply();
+        rgba.extend_from_slice(&[px.red(), px.green(), px.blue(), px.alpha()]);
+    }
+    rgba
+}
 #[pymodule]
 #[pyo3(name = "_rs")]
 mod plt_rs {
     use pyo3::prelude::*;
+    use pyo3::types::PyBytes;
 
+    use super::ErrPath;
+    use super::animation;
+    use super::encode_png;
+    use super::extract_optional_style;
+    use super::pixmap_rgba;
     use super::py_data;
     use super::py_des;
     use super::py_style;
+    use super::raster_params;
+    use super::render_pixmap;
+    use super::save_png_pixmap;
+    use super::strict;
+    use super::window;
 
     #[pyfunction]
+    #[pyo3(signature = (py_fig, path, py_data_src, py_style, size=None, scale=None))]
     fn save_png(
         py_fig: &Bound<'_, PyAny>,
         path: &str,
         py_data_src: &Bound<'_, PyAny>,
         py_style: &Bound<'_, PyAny>,
+        size: Option<(f32, f32)>,
+        scale: Option<f32>,
+    ) -> PyResult<(u32, u32)> {
+        let data_src = py_data::extract_data_source(py_data_src)?;
+        let style = extract_optional_style(py_style)?;
+        let params = raster_params(style, scale);
+        save_png_pixmap(py_fig, path, &*data_src, size, params)
+    }
+
+    /// Raster export options prepared once, to render many figures.
+    #[pyclass]
+    struct Renderer {
+        style: Option<plotive::Style>,
+        scale: Option<f32>,
+    }
+
+    #[pymethods]
+    impl Renderer {
+        #[new]
+        #[pyo3(signature = (py_style, scale=None))]
+        fn new(py_style: &Bound<'_, PyAny>, scale: Option<f32>) -> PyResult<Self> {
+            Ok(Renderer {
+                style: extract_optional_style(py_style)?,
+                scale,
+            })
+        }
+
+        #[pyo3(signature = (py_fig, path, py_data_src, size=None))]
+        fn save_png(
+            &self,
+            py_fig: &Bound<'_, PyAny>,
+            path: &str,
+            py_data_src: &Bound<'_, PyAny>,
+            size: Option<(f32, f32)>,
+        ) -> PyResult<(u32, u32)> {
+            let data_src = py_data::extract_data_source(py_data_src)?;
+            save_png_pixmap(py_fig, path, &*data_src, size, self.params())
+        }
+
+        #[pyo3(signature = (py_fig, py_data_src, size=None))]
+        fn to_png_bytes<'py>(
+            &self,
+            py_fig: &Bound<'py, PyAny>,
+            py_data_src: &Bound<'py, PyAny>,
+            size: Option<(f32, f32)>,
+        ) -> PyResult<Bound<'py, PyBytes>> {
+            let data_src = py_data::extract_data_source(py_data_src)?;
+            let pixmap = render_pixmap(py_fig, &*data_src, size, self.params())?;
+            let png = encode_png(&pixmap)?;
+            Ok(PyBytes::new(py_fig.py(), &png))
+        }
+    }
+
+    impl Renderer {
+        fn params(&self) -> plotive_pxl::Params<'static> {
+            raster_params(self.style.clone(), self.scale)
+        }
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (py_figs, path, py_data_src, py_style, fps, repeat))]
+    fn save_animation(
+        py_figs: &Bound<'_, PyAny>,
+        path: &str,
+        py_data_src: &Bound<'_, PyAny>,
+        py_style: &Bound<'_, PyAny>,
+        fps: f32,
+        repeat: bool,
+    ) -> PyResult<()> {
+        let Some(format) = animation::Format::from_path(path) else {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Cannot deduce the animation format of '{}': expected a .png, .apng or .gif extension.",
+                path
+            )));
+        };
+        if !fps.is_finite() || fps <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "fps must be a positive number. Got {}.",
+                fps
+            )));
+        }
+        let py_figs = py_figs.try_iter()?.collect::<PyResult<Vec<_>>>()?;
+        if py_figs.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "An animation requires at least one figure.",
+            ));
+        }
+        let data_src = py_data::extract_data_source(py_data_src)?;
+        let style = extract_optional_style(py_style)?;
+
+        // frames are encoded as soon as they are rendered, to not hold them all in memory
+        let mut encoder: Option<animation::Encoder> = None;
+        let mut size = (0, 0);
+        for (idx, py_fig) in py_figs.iter().enumerate() {
+            let params = raster_params(style.clone(), None);
+            let pixmap = render_pixmap(py_fig, &*data_src, None, params)
+                .with_path(|| format!("frames[{}]", idx))?;
+            let frame_size = (pixmap.width(), pixmap.height());
+            let encoder = match &mut encoder {
+                Some(encoder) => {
+                    if frame_size != size {
+                        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                            "All frames of an animation must have the same size. Frame {} is {}x{} pixels, expected {}x{}.",
+                            idx, frame_size.0, frame_size.1, size.0, size.1
+                        )));
+                    }
+                    encoder
+                }
+                None => {
+                    size = frame_size;
+                    let created = animation::Encoder::create(
+                        path,
+                        format,
+                        size,
+                        py_figs.len() as u32,
+                        fps,
+                        repeat,
+                    )
+                    .map_err(|e| {
+                        pyo3::exceptions::PyIOError::new_err(format!(
+                            "Failed to save animation: {}",
+                            e
+                        ))
+                    })?;
+                    encoder.insert(created)
+                }
+            };
+            encoder.write_frame(pixmap_rgba(&pixmap)).map_err(|e| {
+                pyo3::exceptions::PyIOError::new_err(format!("Failed to save animation: {}", e))
+            })?;
+        }
+        if let Some(encoder) = encoder {
+            encoder.finish().map_err(|e| {
+                pyo3::exceptions::PyIOError::new_err(format!("Failed to save animation: {}", e))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    #[pyfunction]
+    fn available_styles() -> Vec<&'static str> {
+        py_style::STYLES.iter().map(|(name, _)| *name).collect()
+    }
+
+    #[pyfunction]
+    fn available_themes() -> Vec<&'static str> {
+        py_style::THEMES.iter().map(|(name, _)| *name).collect()
+    }
+
+    #[pyfunction]
+    fn available_palettes() -> Vec<&'static str> {
+        py_style::PALETTES.iter().map(|(name, _)| *name).collect()
+    }
+
+    #[pyfunction]
+    fn set_strict(enabled: bool) {
+        strict::set_strict(enabled);
+    }
+
+    #[pyfunction]
+    fn is_strict() -> bool {
+        strict::is_strict()
+    }
+
+    #[pyfunction]
+    fn set_column_cache(py: Python<'_>, enabled: bool) {
+        py_data::set_column_cache(py, enabled);
+    }
+
+    #[pyfunction]
+    fn clear_column_cache(py: Python<'_>) {
+        py_data::clear_column_cache(py);
+    }
+
+    #[pyfunction]
+    fn validate(py_fig: &Bound<'_, PyAny>, py_data_src: &Bound<'_, PyAny>) -> PyResult<()> {
+        let data_src = py_data::extract_data_source(py_data_src)?;
+        strict::check_figure(py_fig)?;
+        py_des::extract_figure(py_fig, &*data_src)?;
+        py_data::take_read_error()
+    }
+
+    #[pyfunction]
+    fn save_subplots_png(
+        py_fig: &Bound<'_, PyAny>,
+        path_template: &str,
+        py_data_src: &Bound<'_, PyAny>,
+        py_style: &Bound<'_, PyAny>,
     ) -> PyResult<()> {
         use plotive_pxl::SavePng;
+        use rayon::prelude::*;
 
-        let fig = py_des::extract_figure(py_fig)?;
         let data_src = py_data::extract_data_source(py_data_src)?;
-        let mut params: plotive_pxl::Params = Default::default();
-        if !py_style.is_none() {
-            let style = py_style::extract_style(py_style)?;
-            params.style = style;
+        strict::check_figure(py_fig)?;
+        let figs = py_des::extract_plot_figures(py_fig, &*data_src)?;
+        if figs.len() > 1 && !path_template.contains("{}") {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Path template '{}' must contain a '{{}}' placeholder to save {} subplots.",
+                path_template,
+                figs.len()
+            )));
         }
-        fig.save_png(path, &*data_src, params).map_err(|e| {
-            pyo3::exceptions::PyIOError::new_err(format!("Failed to save PNG: {}", e))
-        })?;
+        let style = extract_optional_style(py_style)?;
+        py_style::check_palette_cycle(py_fig, &style.clone().unwrap_or_default())?;
 
-        Ok(())
+        // The subplots are independent figures, rendered in parallel with the GIL released.
+        // The data source borrows python objects, so it is copied to be shared between threads.
+        let data_src = py_data::SharedDataSource::copy_from(&*data_src);
+        py_data::take_read_error()?;
+        let save = |(idx, fig): (usize, &plotive::des::Figure)| -> PyResult<()> {
+            // subplots are numbered from 1, as in the python API
+            let path = path_template.replace("{}", &(idx + 1).to_string());
+            let params = raster_params(style.clone(), None);
+            fig.save_png(&path, &data_src, params).map_err(|e| {
+                pyo3::exceptions::PyIOError::new_err(format!("Failed to save PNG: {}", e))
+            })
+        };
+        py_fig
+            .py()
+            .detach(|| figs.par_iter().enumerate().try_for_each(save))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (py_fig, py_data_src, py_style, size=None, scale=None))]
+    fn to_png_bytes<'py>(
+        py_fig: &Bound<'py, PyAny>,
+        py_data_src: &Bound<'py, PyAny>,
+        py_style: &Bound<'py, PyAny>,
+        size: Option<(f32, f32)>,
+        scale: Option<f32>,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let data_src = py_data::extract_data_source(py_data_src)?;
+        let style = extract_optional_style(py_style)?;
+        let params = raster_params(style, scale);
+        let pixmap = render_pixmap(py_fig, &*data_src, size, params)?;
+        let png = encode_png(&pixmap)?;
+
+        Ok(PyBytes::new(py_fig.py(), &png))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (py_fig, py_data_src, py_style, size=None, scale=None))]
+    fn to_rgba_array<'py>(
+        py_fig: &Bound<'py, PyAny>,
+        py_data_src: &Bound<'py, PyAny>,
+        py_style: &Bound<'py, PyAny>,
+        size: Option<(f32, f32)>,
+        scale: Option<f32>,
+    ) -> PyResult<Bound<'py, numpy::PyArray3<u8>>> {
+        use numpy::PyArrayMethods;
+
+        let data_src = py_data::extract_data_source(py_data_src)?;
+        let style = extract_optional_style(py_style)?;
+        let params = raster_params(style, scale);
+        let pixmap = render_pixmap(py_fig, &*data_src, size, params)?;
+
+        let rgba = pixmap_rgba(&pixmap);
+        let (w, h) = (pixmap.width() as usize, pixmap.height() as usize);
+        numpy::PyArray1::from_vec(py_fig.py(), rgba).reshape([h, w, 4])
     }
 
     #[pyfunction]
@@ -94,37 +534,54 @@ mod plt_rs {
     ) -> PyResult<()> {
         use plotive_svg::SaveSvg;
 
-        let fig = py_des::extract_figure(py_fig)?;
         let data_src = py_data::extract_data_source(py_data_src)?;
+        strict::check_figure(py_fig)?;
+        let fig = py_des::extract_figure(py_fig, &*data_src)?;
         let mut params: plotive_svg::Params = Default::default();
         if !py_style.is_none() {
             let style = py_style::extract_style(py_style)?;
             params.style = style;
         }
+        py_style::check_palette_cycle(py_fig, &params.style)?;
         fig.save_svg(path, &*data_src, params).map_err(|e| {
             pyo3::exceptions::PyIOError::new_err(format!("Failed to save SVG: {}", e))
         })?;
-
-        Ok(())
+        py_data::take_read_error()
     }
 
     #[pyfunction]
+    #[pyo3(signature = (py_fig, py_data_src, py_style, title=None, size=None, navigation=true))]
     fn show(
         py_fig: &Bound<'_, PyAny>,
         py_data_src: &Bound<'_, PyAny>,
         py_style: &Bound<'_, PyAny>,
+        title: Option<String>,
+        size: Option<(f32, f32)>,
+        navigation: bool,
     ) -> PyResult<()> {
-        use plotive_iced::Show;
+        use plotive::Prepare;
 
-        let fig = py_des::extract_figure(py_fig)?;
-        // show requires 'static lifetime, so we need to copy the data source
-        let data_src = py_data::extract_data_source(py_data_src)?.copy();
-        let mut params: plotive_iced::show::Params = Default::default();
-        if !py_style.is_none() {
-            let style = py_style::extract_style(py_style)?;
-            params.style = Some(style);
-        }
-        fig.show(data_src, params).map_err(|e| {
+        let data_src = py_data::extract_data_source(py_data_src)?;
+        strict::check_figure(py_fig)?;
+        let fig = py_des::extract_figure(py_fig, &*data_src)?;
+        let style = extract_optional_style(py_style)?;
+        py_style::check_palette_cycle(py_fig, &style.clone().unwrap_or_default())?;
+        // prepared here, so that an invalid figure raises instead of failing in the window
+        let fontdb = std::sync::Arc::new(plotive::bundled_font_db());
+        let fig = fig.prepare(&*data_src, Some(&*fontdb)).map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to show figure: {}", e))
+        })?;
+        // the window requires 'static lifetime, so we need to copy the data source
+        let data_src = data_src.copy();
+        py_data::take_read_error()?;
+        let params = window::Params {
+            style,
+            fontdb,
+            title: title.unwrap_or_else(|| "plotive".to_string()),
+            size,
+            navigation,
+        };
+        window::show(fig, data_src, params).map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to show figure: {}", e))
         })?;
 
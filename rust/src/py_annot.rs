@@ -40,10 +40,10 @@ pub fn extract_annot(py_annot: &Bound<'_, PyAny>) -> PyResult<des::Annotation> {
 
 fn extract_line_annot(py_line: &Bound<'_, PyAny>) -> PyResult<des::annot::Line> {
     let mut line = if let Some(py_y) = super::getattr_not_none(py_line, "horizontal")? {
-        let y = py_y.extract::<f64>()?;
+        let y = super::extract_f64_like(&py_y)?;
         des::annot::Line::horizontal(y)
     } else if let Some(py_x) = super::getattr_not_none(py_line, "vertical")? {
-        let x = py_x.extract::<f64>()?;
+        let x = super::extract_f64_like(&py_x)?;
         des::annot::Line::vertical(x)
     } else if let Some(py_slope) = super::getattr_not_none(py_line, "slope")? {
         let ((x,  y), slope) = py_slope.extract::<((f64, f64), f32)>()?;
@@ -66,13 +66,13 @@ fn extract_line_annot(py_line: &Bound<'_, PyAny>) -> PyResult<des::annot::Line>
 }
 
 fn extract_arrow_annot(py_annot: &Bound<'_, PyAny>) -> PyResult<des::annot::Arrow> {
-    let x = py_annot.getattr("x")?.extract::<f64>()?;
-    let y = py_annot.getattr("y")?.extract::<f64>()?;
-    let dx = py_annot.getattr("dx")?.extract::<f32>()?;
-    let dy = py_annot.getattr("dy")?.extract::<f32  >()?;
+    let x = super::extract_f64_like(&py_annot.getattr("x")?)?;
+    let y = super::extract_f64_like(&py_annot.getattr("y")?)?;
+    let dx = super::extract_f32_like(&py_annot.getattr("dx")?)?;
+    let dy = super::extract_f32_like(&py_annot.getattr("dy")?)?;
     let mut arrow = des::annot::Arrow::new(x, y, dx, dy);
     if let Some(py_head_size) = super::getattr_not_none(py_annot, "head_size")? {
-        let head_size = py_head_size.extract::<f32>()?;
+        let head_size = super::extract_f32_like(&py_head_size)?;
         arrow = arrow.with_head_size(head_size);
     }
     if let Some(py_stroke) = super::getattr_not_none(py_annot, "stroke")? {
@@ -83,8 +83,8 @@ fn extract_arrow_annot(py_annot: &Bound<'_, PyAny>) -> PyResult<des::annot::Arro
 }
 
 fn extract_label_annot(py_annot: &Bound<'_, PyAny>) -> PyResult<des::annot::Label> {
-    let x = py_annot.getattr("x")?.extract::<f64>()?;
-    let y = py_annot.getattr("y")?.extract::<f64>()?;
+    let x = super::extract_f64_like(&py_annot.getattr("x")?)?;
+    let y = super::extract_f64_like(&py_annot.getattr("y")?)?;
     let text = py_annot.getattr("text")?.extract::<String>()?;
     let mut label = des::annot::Label::new(text, x, y);
     if let Some(py_anchor) = super::getattr_not_none(py_annot, "anchor")? {
@@ -112,7 +112,7 @@ fn extract_label_annot(py_annot: &Bound<'_, PyAny>) -> PyResult<des::annot::Labe
         label = label.with_color(color);
     }
     if let Some(py_angle) = super::getattr_not_none(py_annot, "angle")? {
-        let angle = py_angle.extract::<f32>()?;
+        let angle = super::extract_f32_like(&py_angle)?;
         label = label.with_angle(angle);
     }
     if let Some(py_frame) = super::getattr_not_none(py_annot, "frame")? {
@@ -1,19 +1,47 @@
-use std::sync::Arc;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 use numpy::PyArrayMethods;
 use plotive::data;
-use pyo3::{prelude::*, types::PyDict};
+use pyo3::{
+    prelude::*,
+    sync::MutexExt,
+    types::{PyDict, PyList, PyString},
+};
 
+use super::{extract_class_name, getattr_not_none};
+
+// plotive takes the data source behind an `Arc`, but renders on the calling thread only
+#[allow(clippy::arc_with_non_send_sync)]
 pub fn extract_data_source<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Arc<dyn data::Source + 'py>> {
+    // errors of a previous render that failed before taking them
+    READ_ERROR.with(|err| err.borrow_mut().take());
     if obj.is_none() {
         Ok(Arc::new(()))
     } else if obj.is_instance_of::<pyo3::types::PyDict>() {
         let dict = obj.cast::<PyDict>()?;
         let ds = extract_dict_data_source(dict.clone())?;
         Ok(Arc::new(ds))
+    } else if let Ok(array) = obj.cast::<numpy::PyUntypedArray>() {
+        let ds = extract_array_data_source(array.clone())?;
+        Ok(Arc::new(ds))
+    } else if is_records_list(obj) {
+        let list = obj.cast::<PyList>()?;
+        let ds = extract_records_data_source(list.clone())?;
+        Ok(Arc::new(ds))
+    } else if let Ok(list) = obj.cast::<PyList>() {
+        let ds = extract_list_data_source(list.clone())?;
+        Ok(Arc::new(ds))
     } else if is_pandas_dataframe(obj)? {
         let ds = extract_pandas_data_source(obj.clone())?;
         Ok(Arc::new(ds))
+    } else if is_polars_dataframe(obj)? {
+        let ds = extract_polars_data_source(obj.clone())?;
+        Ok(Arc::new(ds))
+    } else if is_arrow_table(obj)? {
+        let ds = extract_arrow_data_source(obj.clone())?;
+        Ok(Arc::new(ds))
     } else {
         Err(pyo3::exceptions::PyTypeError::new_err(
             "Data source could not be extracted.",
@@ -21,6 +49,13 @@ pub fn extract_data_source<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Arc<dyn dat
     }
 }
 
+fn is_records_list(obj: &Bound<'_, PyAny>) -> bool {
+    let Ok(list) = obj.cast::<PyList>() else {
+        return false;
+    };
+    !list.is_empty() && list.iter().all(|item| item.is_instance_of::<PyDict>())
+}
+
 fn is_pandas_dataframe(obj: &Bound<'_, PyAny>) -> PyResult<bool> {
     if let Ok(module) = obj.py().import("pandas") {
         let df_class = module.getattr("DataFrame")?;
@@ -30,6 +65,46 @@ fn is_pandas_dataframe(obj: &Bound<'_, PyAny>) -> PyResult<bool> {
     }
 }
 
+fn is_polars_dataframe(obj: &Bound<'_, PyAny>) -> PyResult<bool> {
+    if let Ok(module) = obj.py().import("polars") {
+        let df_class = module.getattr("DataFrame")?;
+        Ok(obj.is_instance(&df_class)?)
+    } else {
+        Ok(false)
+    }
+}
+
+fn is_arrow_table(obj: &Bound<'_, PyAny>) -> PyResult<bool> {
+    if let Ok(module) = obj.py().import("pyarrow") {
+        let table_class = module.getattr("Table")?;
+        Ok(obj.is_instance(&table_class)?)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Detect a `numpy.ma.MaskedArray` and fill the masked entries with NaN.
+/// Masked arrays are ndarrays, so this must be checked before the plain arrays.
+fn is_numpy_masked_array<'py>(
+    obj: &Bound<'py, PyAny>,
+) -> Option<numpy::borrow::PyReadonlyArray1<'py, f64>> {
+    let masked_class = obj
+        .py()
+        .import("numpy.ma")
+        .ok()?
+        .getattr("MaskedArray")
+        .ok()?;
+    if !obj.is_instance(&masked_class).ok()? {
+        return None;
+    }
+    let filled = obj
+        .call_method1("astype", ("float64",))
+        .and_then(|a| a.call_method1("filled", (f64::NAN,)))
+        .ok()?;
+    let filled = filled.cast::<numpy::PyArray1<f64>>().ok()?;
+    Some(filled.readonly())
+}
+
 fn is_numpy_array_f64<'py>(
     obj: &Bound<'py, PyAny>,
 ) -> Option<numpy::borrow::PyReadonlyArray1<'py, f64>> {
@@ -60,50 +135,235 @@ fn is_numpy_array_i64<'py>(
     }
 }
 
+fn is_numpy_array_i32<'py>(
+    obj: &Bound<'py, PyAny>,
+) -> Option<numpy::borrow::PyReadonlyArray1<'py, i32>> {
+    if let Ok(py_array) = obj.cast::<numpy::PyArray1<i32>>() {
+        Some(py_array.readonly())
+    } else {
+        None
+    }
+}
+
+fn is_numpy_array_u8<'py>(
+    obj: &Bound<'py, PyAny>,
+) -> Option<numpy::borrow::PyReadonlyArray1<'py, u8>> {
+    if let Ok(py_array) = obj.cast::<numpy::PyArray1<u8>>() {
+        Some(py_array.readonly())
+    } else {
+        None
+    }
+}
+
+fn is_numpy_array_bool<'py>(
+    obj: &Bound<'py, PyAny>,
+) -> Option<numpy::borrow::PyReadonlyArray1<'py, bool>> {
+    if let Ok(py_array) = obj.cast::<numpy::PyArray1<bool>>() {
+        Some(py_array.readonly())
+    } else {
+        None
+    }
+}
+
+/// Detect a `datetime64` array and view it as i64 values.
+/// Returns the values and the number of nanoseconds per value unit.
+fn is_numpy_array_datetime<'py>(
+    obj: &Bound<'py, PyAny>,
+) -> Option<(numpy::borrow::PyReadonlyArray1<'py, i64>, i64)> {
+    let dtype = obj.getattr("dtype").ok()?;
+    let kind: String = dtype.getattr("kind").ok()?.extract().ok()?;
+    if kind != "M" {
+        return None;
+    }
+    // plotive has no time zones, so tz-aware pandas columns are read as local times
+    if let Ok(tz) = dtype.getattr("tz")
+        && !tz.is_none()
+    {
+        let py = obj.py();
+        let local = match obj.getattr("dt") {
+            Ok(dt) => dt.call_method1("tz_localize", (py.None(),)),
+            // DatetimeIndex
+            Err(_) => obj.call_method1("tz_localize", (py.None(),)),
+        };
+        return is_numpy_array_datetime(&local.ok()?);
+    }
+    let np = obj.py().import("numpy").ok()?;
+    let (unit, count): (String, i64) = np
+        .call_method1("datetime_data", (&dtype,))
+        .ok()?
+        .extract()
+        .ok()?;
+    let unit_ns = match unit.as_str() {
+        "ns" => 1,
+        "us" => 1_000,
+        "ms" => 1_000_000,
+        "s" => 1_000_000_000,
+        "m" => 60_000_000_000,
+        "h" => 3_600_000_000_000,
+        "D" => 86_400_000_000_000,
+        "W" => 604_800_000_000_000,
+        // months and years have no fixed duration, numpy converts them for us
+        _ => {
+            let converted = obj.call_method1("astype", ("datetime64[s]",)).ok()?;
+            return is_numpy_array_datetime(&converted);
+        }
+    };
+    // asarray lets pandas datetime series through as well
+    let values = np
+        .call_method1("asarray", (obj,))
+        .and_then(|a| a.call_method1("view", ("int64",)))
+        .ok()?;
+    let values = values.cast::<numpy::PyArray1<i64>>().ok()?;
+    Some((values.readonly(), unit_ns * count))
+}
+
 #[derive(Debug)]
 enum NumpyColumn<'py> {
     F64(numpy::borrow::PyReadonlyArray1<'py, f64>),
     F32(numpy::borrow::PyReadonlyArray1<'py, f32>),
     I64(numpy::borrow::PyReadonlyArray1<'py, i64>),
+    I32(numpy::borrow::PyReadonlyArray1<'py, i32>),
+    U8(numpy::borrow::PyReadonlyArray1<'py, u8>),
+    /// Booleans are exposed as 0/1 integers, e.g. for masks
+    Bool(numpy::borrow::PyReadonlyArray1<'py, bool>),
+    /// Pandas nullable integers. Values are kept as i64 and masked entries (NA) are None.
+    /// For plotting, they are exposed as f64 as well, like the other integer columns.
+    NullableI64 {
+        values: numpy::borrow::PyReadonlyArray1<'py, i64>,
+        mask: numpy::borrow::PyReadonlyArray1<'py, bool>,
+    },
+    /// `datetime64` values as i64 epoch values, counted in `unit_ns` nanoseconds.
+    /// NaT is `i64::MIN`.
+    DateTime {
+        values: numpy::borrow::PyReadonlyArray1<'py, i64>,
+        unit_ns: i64,
+    },
+    /// Strings, e.g. for categorical axes. Unlike the other variants, values are copied.
+    Str(Vec<Option<String>>),
+    /// Values read by chunks from an out-of-core array, exposed as f64.
+    Lazy(LazyColumn<'py>),
+}
+
+/// Element of a numpy array read as a value of a f64 column. Non-finite values are missing.
+trait NumpyElement: Copy {
+    fn to_f64(self) -> Option<f64>;
+}
+
+/// Element of an integer or boolean numpy array, read as a value of a i64 column.
+trait NumpyIntElement: NumpyElement {
+    fn to_i64(self) -> Option<i64>;
+}
+
+impl NumpyElement for f64 {
+    fn to_f64(self) -> Option<f64> {
+        self.is_finite().then_some(self)
+    }
+}
+
+impl NumpyElement for f32 {
+    fn to_f64(self) -> Option<f64> {
+        self.is_finite().then_some(self as f64)
+    }
+}
+
+macro_rules! impl_numpy_int_element {
+    ($($ty:ty),*) => {$(
+        impl NumpyElement for $ty {
+            fn to_f64(self) -> Option<f64> {
+                Some(self as f64)
+            }
+        }
+
+        impl NumpyIntElement for $ty {
+            fn to_i64(self) -> Option<i64> {
+                Some(self as i64)
+            }
+        }
+    )*};
 }
 
-struct NumpyF64Iter<'py> {
-    array: numpy::borrow::PyReadonlyArray1<'py, f64>,
+impl_numpy_int_element!(i64, i32, u8);
+
+impl NumpyElement for bool {
+    fn to_f64(self) -> Option<f64> {
+        Some(if self { 1.0 } else { 0.0 })
+    }
+}
+
+impl NumpyIntElement for bool {
+    fn to_i64(self) -> Option<i64> {
+        Some(self as i64)
+    }
+}
+
+/// Iterator over a numpy array, converting each element to a column value.
+struct NumpyIter<'a, T: Copy, U> {
+    array: numpy::ndarray::ArrayView1<'a, T>,
+    convert: fn(T) -> Option<U>,
     index: usize,
 }
 
-impl<'py> Iterator for NumpyF64Iter<'py> {
-    type Item = Option<f64>;
+impl<'a, T: Copy, U> NumpyIter<'a, T, U> {
+    fn new(array: numpy::ndarray::ArrayView1<'a, T>, convert: fn(T) -> Option<U>) -> Self {
+        NumpyIter {
+            array,
+            convert,
+            index: 0,
+        }
+    }
+}
+
+impl<T: Copy, U> Iterator for NumpyIter<'_, T, U> {
+    type Item = Option<U>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let array = self.array.as_array();
-        if self.index < array.len() {
-            let value = array[self.index];
+        if self.index < self.array.len() {
+            let value = self.array[self.index];
             self.index += 1;
-            Some(if value.is_finite() { Some(value) } else { None })
+            Some((self.convert)(value))
         } else {
             None
         }
     }
 }
 
-struct NumpyF32Iter<'py> {
-    array: numpy::borrow::PyReadonlyArray1<'py, f32>,
+/// Iterator over a numpy array with a mask of missing values (`True` where missing).
+struct NumpyMaskedIter<'a, T: Copy, U> {
+    values: NumpyIter<'a, T, U>,
+    mask: numpy::ndarray::ArrayView1<'a, bool>,
+}
+
+impl<T: Copy, U> Iterator for NumpyMaskedIter<'_, T, U> {
+    type Item = Option<U>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let masked = *self.mask.get(self.values.index)?;
+        let value = self.values.next()?;
+        Some(if masked { None } else { value })
+    }
+}
+
+struct NumpyDateTimeIter<'a> {
+    array: numpy::ndarray::ArrayView1<'a, i64>,
+    unit_ns: i64,
     index: usize,
 }
 
-impl<'py> Iterator for NumpyF32Iter<'py> {
-    type Item = Option<f64>;
+impl Iterator for NumpyDateTimeIter<'_> {
+    type Item = Option<plotive::time::DateTime>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let array = self.array.as_array();
-        if self.index < array.len() {
-            let value = array[self.index];
+        if self.index < self.array.len() {
+            let value = self.array[self.index];
             self.index += 1;
-            Some(if value.is_finite() {
-                Some(value as f64)
-            } else {
+            Some(if value == i64::MIN {
                 None
+            } else {
+                // plotive timestamps are seconds since its own epoch, not the unix epoch
+                let secs = value as f64 * self.unit_ns as f64 * 1e-9;
+                plotive::time::DateTime::from_timestamp(
+                    plotive::time::DateTime::unix_epoch().timestamp() + secs,
+                )
             })
         } else {
             None
@@ -111,43 +371,163 @@ impl<'py> Iterator for NumpyF32Iter<'py> {
     }
 }
 
-struct NumpyI64toF64Iter<'py> {
-    array: numpy::borrow::PyReadonlyArray1<'py, i64>,
-    index: usize,
+/// A column read by chunks from a python array-like supporting `len()` and slicing,
+/// such as an h5py dataset or a zarr array, for data too large to fit in memory.
+/// Values are pulled while iterating, so that only one chunk is materialized at a time.
+#[derive(Debug)]
+struct LazyColumn<'py> {
+    source: Bound<'py, PyAny>,
+    len: usize,
+    /// `start..stop` bounds of the chunks, in order
+    chunks: Vec<(usize, usize)>,
+    /// Statistics gathered by the first complete iteration
+    stats: Cell<Option<LazyStats>>,
+    /// Set when a chunk failed to read, after which the remaining values are missing
+    failed: Cell<bool>,
 }
 
-impl<'py> Iterator for NumpyI64toF64Iter<'py> {
-    type Item = Option<f64>;
+/// Statistics of a lazy column, so that the whole column is not read again for them.
+#[derive(Debug, Clone, Copy)]
+struct LazyStats {
+    len_some: usize,
+    minmax: Option<(f64, f64)>,
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let array = self.array.as_array();
-        if self.index < array.len() {
-            let value = array[self.index] as f64;
-            self.index += 1;
-            Some(Some(value))
-        } else {
-            None
+thread_local! {
+    /// First error raised while reading a lazy column during the current render.
+    static READ_ERROR: RefCell<Option<PyErr>> = const { RefCell::new(None) };
+}
+
+/// Raise the first error met while reading lazy columns since the data source was extracted.
+///
+/// The column iterators of plotive cannot fail, so the chunks that cannot be read are
+/// rendered as missing values and the error is kept until the render completes.
+pub fn take_read_error() -> PyResult<()> {
+    match READ_ERROR.with(|err| err.borrow_mut().take()) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+impl LazyColumn<'_> {
+    /// Read the values of `start..stop`.
+    /// On failure, the error is kept for `take_read_error` and the values are missing.
+    fn read_chunk(&self, start: usize, stop: usize) -> Vec<f64> {
+        if self.failed.get() {
+            return vec![f64::NAN; stop - start];
+        }
+        match self.read_chunk_values(start, stop) {
+            Ok(values) => values,
+            Err(err) => {
+                self.failed.set(true);
+                READ_ERROR.with(|read_err| {
+                    read_err.borrow_mut().get_or_insert(err);
+                });
+                vec![f64::NAN; stop - start]
+            }
+        }
+    }
+
+    fn stats(&self) -> LazyStats {
+        if let Some(stats) = self.stats.get() {
+            return stats;
+        }
+        let mut iter = LazyF64Iter::new(self);
+        for _ in iter.by_ref() {}
+        iter.stats
+    }
+
+    fn read_chunk_values(&self, start: usize, stop: usize) -> PyResult<Vec<f64>> {
+        let py = self.source.py();
+        let slice = pyo3::types::PySlice::new(py, start as isize, stop as isize, 1);
+        let chunk = self.source.get_item(slice)?;
+        let array = py
+            .import("numpy")?
+            .call_method1("asarray", (chunk, "float64"))?;
+        let array = array.cast_into::<numpy::PyArray1<f64>>()?;
+        let values = array.readonly().as_array().to_vec();
+        if values.len() != stop - start {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "LazyColumn slice [{}:{}] returned {} values instead of {}.",
+                start,
+                stop,
+                values.len(),
+                stop - start
+            )));
         }
+        Ok(values)
+    }
+}
+
+impl data::F64Column for LazyColumn<'_> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn f64_iter(&self) -> Box<dyn Iterator<Item = Option<f64>> + '_> {
+        Box::new(LazyF64Iter::new(self))
+    }
+
+    fn minmax(&self) -> Option<(f64, f64)> {
+        self.stats().minmax
     }
 }
 
-struct NumpyI64Iter<'py> {
-    array: numpy::borrow::PyReadonlyArray1<'py, i64>,
+struct LazyF64Iter<'a, 'py> {
+    column: &'a LazyColumn<'py>,
+    chunk: Vec<f64>,
+    chunk_start: usize,
+    /// Index of the next chunk to read
+    next_chunk: usize,
     index: usize,
+    /// Statistics of the values iterated so far
+    stats: LazyStats,
 }
 
-impl<'py> Iterator for NumpyI64Iter<'py> {
-    type Item = Option<i64>;
+impl<'a, 'py> LazyF64Iter<'a, 'py> {
+    fn new(column: &'a LazyColumn<'py>) -> Self {
+        LazyF64Iter {
+            column,
+            chunk: Vec::new(),
+            chunk_start: 0,
+            next_chunk: 0,
+            index: 0,
+            stats: LazyStats {
+                len_some: 0,
+                minmax: None,
+            },
+        }
+    }
+}
+
+impl Iterator for LazyF64Iter<'_, '_> {
+    type Item = Option<f64>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let array = self.array.as_array();
-        if self.index < array.len() {
-            let value = array[self.index];
-            self.index += 1;
-            Some(Some(value))
-        } else {
-            None
+        if self.index >= self.column.len {
+            // statistics of a column that failed to read would not be those of the source
+            if !self.column.failed.get() && self.column.stats.get().is_none() {
+                self.column.stats.set(Some(self.stats));
+            }
+            return None;
+        }
+        if self.index >= self.chunk_start + self.chunk.len() {
+            let (start, stop) = self.column.chunks[self.next_chunk];
+            self.chunk = self.column.read_chunk(start, stop);
+            self.chunk_start = start;
+            self.next_chunk += 1;
+        }
+        let value = self.chunk[self.index - self.chunk_start];
+        self.index += 1;
+        if !value.is_finite() {
+            return Some(None);
         }
+        self.stats.len_some += 1;
+        self.stats.minmax = match self.stats.minmax {
+            Some((min, max)) => Some((min.min(value), max.max(value))),
+            None => Some((value, value)),
+        };
+        Some(Some(value))
     }
 }
 
@@ -157,6 +537,13 @@ impl data::Column for NumpyColumn<'_> {
             NumpyColumn::F64(col) => col.len().unwrap_or(0),
             NumpyColumn::F32(col) => col.len().unwrap_or(0),
             NumpyColumn::I64(col) => col.len().unwrap_or(0),
+            NumpyColumn::I32(col) => col.len().unwrap_or(0),
+            NumpyColumn::U8(col) => col.len().unwrap_or(0),
+            NumpyColumn::Bool(col) => col.len().unwrap_or(0),
+            NumpyColumn::NullableI64 { values, .. } => values.len().unwrap_or(0),
+            NumpyColumn::DateTime { values, .. } => values.len().unwrap_or(0),
+            NumpyColumn::Str(col) => col.len(),
+            NumpyColumn::Lazy(col) => col.len,
         }
     }
 
@@ -165,6 +552,17 @@ impl data::Column for NumpyColumn<'_> {
             NumpyColumn::F64(col) => col.as_array().iter().filter(|v| v.is_finite()).count(),
             NumpyColumn::F32(col) => col.as_array().iter().filter(|v| v.is_finite()).count(),
             NumpyColumn::I64(col) => col.as_array().len(),
+            NumpyColumn::I32(col) => col.as_array().len(),
+            NumpyColumn::U8(col) => col.as_array().len(),
+            NumpyColumn::Bool(col) => col.as_array().len(),
+            NumpyColumn::NullableI64 { mask, .. } => {
+                mask.as_array().iter().filter(|m| !**m).count()
+            }
+            NumpyColumn::DateTime { values, .. } => {
+                values.as_array().iter().filter(|v| **v != i64::MIN).count()
+            }
+            NumpyColumn::Str(col) => col.iter().filter(|v| v.is_some()).count(),
+            NumpyColumn::Lazy(col) => col.stats().len_some,
         }
     }
 
@@ -173,12 +571,37 @@ impl data::Column for NumpyColumn<'_> {
             NumpyColumn::F64(_) => Some(self),
             NumpyColumn::F32(_) => Some(self),
             NumpyColumn::I64(_) => Some(self),
+            NumpyColumn::I32(_) => Some(self),
+            NumpyColumn::U8(_) => Some(self),
+            NumpyColumn::Bool(_) => Some(self),
+            NumpyColumn::NullableI64 { .. } => Some(self),
+            NumpyColumn::Lazy(_) => Some(self),
+            NumpyColumn::DateTime { .. } => None,
+            NumpyColumn::Str(_) => None,
         }
     }
 
     fn i64(&self) -> Option<&dyn data::I64Column> {
         match self {
-            NumpyColumn::I64(_) => Some(self),
+            NumpyColumn::I64(_)
+            | NumpyColumn::I32(_)
+            | NumpyColumn::U8(_)
+            | NumpyColumn::Bool(_)
+            | NumpyColumn::NullableI64 { .. } => Some(self),
+            _ => None,
+        }
+    }
+
+    fn str(&self) -> Option<&dyn data::StrColumn> {
+        match self {
+            NumpyColumn::Str(_) => Some(self),
+            _ => None,
+        }
+    }
+
+    fn time(&self) -> Option<&dyn data::TimeColumn> {
+        match self {
+            NumpyColumn::DateTime { .. } => Some(self),
             _ => None,
         }
     }
@@ -190,39 +613,101 @@ impl data::F64Column for NumpyColumn<'_> {
             NumpyColumn::F64(col) => col.len().unwrap_or(0),
             NumpyColumn::F32(col) => col.len().unwrap_or(0),
             NumpyColumn::I64(col) => col.len().unwrap_or(0),
+            NumpyColumn::I32(col) => col.len().unwrap_or(0),
+            NumpyColumn::U8(col) => col.len().unwrap_or(0),
+            NumpyColumn::Bool(col) => col.len().unwrap_or(0),
+            NumpyColumn::NullableI64 { values, .. } => values.len().unwrap_or(0),
+            NumpyColumn::DateTime { values, .. } => values.len().unwrap_or(0),
+            NumpyColumn::Str(_) => 0,
+            NumpyColumn::Lazy(col) => col.len,
         }
     }
 
     fn f64_iter(&self) -> Box<dyn Iterator<Item = Option<f64>> + '_> {
         match self {
-            NumpyColumn::F64(col) => Box::new(NumpyF64Iter {
-                array: col.clone(),
-                index: 0,
-            }),
-            NumpyColumn::F32(col) => Box::new(NumpyF32Iter {
-                array: col.clone(),
-                index: 0,
-            }),
-            NumpyColumn::I64(col) => Box::new(NumpyI64toF64Iter {
-                array: col.clone(),
-                index: 0,
+            NumpyColumn::F64(col) => Box::new(NumpyIter::new(col.as_array(), f64::to_f64)),
+            NumpyColumn::F32(col) => Box::new(NumpyIter::new(col.as_array(), f32::to_f64)),
+            NumpyColumn::I64(col) => Box::new(NumpyIter::new(col.as_array(), i64::to_f64)),
+            NumpyColumn::I32(col) => Box::new(NumpyIter::new(col.as_array(), i32::to_f64)),
+            NumpyColumn::U8(col) => Box::new(NumpyIter::new(col.as_array(), u8::to_f64)),
+            NumpyColumn::Bool(col) => Box::new(NumpyIter::new(col.as_array(), bool::to_f64)),
+            NumpyColumn::NullableI64 { values, mask } => Box::new(NumpyMaskedIter {
+                values: NumpyIter::new(values.as_array(), i64::to_f64),
+                mask: mask.as_array(),
             }),
+            NumpyColumn::Lazy(col) => data::F64Column::f64_iter(col),
+            NumpyColumn::DateTime { .. } => Box::new(std::iter::empty()),
+            NumpyColumn::Str(_) => Box::new(std::iter::empty()),
         }
     }
+
+    fn minmax(&self) -> Option<(f64, f64)> {
+        if let NumpyColumn::Lazy(col) = self {
+            return data::F64Column::minmax(col);
+        }
+        self.f64_iter().flatten().fold(None, |res, v| match res {
+            Some((min, max)) => Some((v.min(min), v.max(max))),
+            None => Some((v, v)),
+        })
+    }
 }
 
 impl data::I64Column for NumpyColumn<'_> {
     fn len(&self) -> usize {
         match self {
             NumpyColumn::I64(col) => col.len().unwrap_or(0),
+            NumpyColumn::I32(col) => col.len().unwrap_or(0),
+            NumpyColumn::U8(col) => col.len().unwrap_or(0),
+            NumpyColumn::Bool(col) => col.len().unwrap_or(0),
+            NumpyColumn::NullableI64 { values, .. } => values.len().unwrap_or(0),
             _ => 0,
         }
     }
 
     fn i64_iter(&self) -> Box<dyn Iterator<Item = Option<i64>> + '_> {
         match self {
-            NumpyColumn::I64(col) => Box::new(NumpyI64Iter {
-                array: col.clone(),
+            NumpyColumn::I64(col) => Box::new(NumpyIter::new(col.as_array(), i64::to_i64)),
+            NumpyColumn::I32(col) => Box::new(NumpyIter::new(col.as_array(), i32::to_i64)),
+            NumpyColumn::U8(col) => Box::new(NumpyIter::new(col.as_array(), u8::to_i64)),
+            NumpyColumn::Bool(col) => Box::new(NumpyIter::new(col.as_array(), bool::to_i64)),
+            NumpyColumn::NullableI64 { values, mask } => Box::new(NumpyMaskedIter {
+                values: NumpyIter::new(values.as_array(), i64::to_i64),
+                mask: mask.as_array(),
+            }),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+impl data::StrColumn for NumpyColumn<'_> {
+    fn len(&self) -> usize {
+        match self {
+            NumpyColumn::Str(col) => col.len(),
+            _ => 0,
+        }
+    }
+
+    fn str_iter(&self) -> Box<dyn Iterator<Item = Option<&str>> + '_> {
+        match self {
+            NumpyColumn::Str(col) => Box::new(col.iter().map(|v| v.as_deref())),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+impl data::TimeColumn for NumpyColumn<'_> {
+    fn len(&self) -> usize {
+        match self {
+            NumpyColumn::DateTime { values, .. } => values.len().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    fn time_iter(&self) -> Box<dyn Iterator<Item = Option<plotive::time::DateTime>> + '_> {
+        match self {
+            NumpyColumn::DateTime { values, unit_ns } => Box::new(NumpyDateTimeIter {
+                array: values.as_array(),
+                unit_ns: *unit_ns,
                 index: 0,
             }),
             _ => Box::new(std::iter::empty()),
@@ -230,10 +715,57 @@ impl data::I64Column for NumpyColumn<'_> {
     }
 }
 
+/// A column of a data source, caching its count of non-missing values.
+/// Counting them scans the whole column, and the renderer may ask for it several times.
+#[derive(Debug)]
+struct SourceColumn<'py> {
+    column: NumpyColumn<'py>,
+    len_some: OnceCell<usize>,
+}
+
+impl data::Column for SourceColumn<'_> {
+    fn len(&self) -> usize {
+        self.column.len()
+    }
+
+    fn len_some(&self) -> usize {
+        *self.len_some.get_or_init(|| self.column.len_some())
+    }
+
+    fn f64(&self) -> Option<&dyn data::F64Column> {
+        self.column.f64()
+    }
+
+    fn i64(&self) -> Option<&dyn data::I64Column> {
+        self.column.i64()
+    }
+
+    fn str(&self) -> Option<&dyn data::StrColumn> {
+        self.column.str()
+    }
+
+    fn time(&self) -> Option<&dyn data::TimeColumn> {
+        self.column.time()
+    }
+}
+
 #[derive(Debug)]
 struct NumpyDataSource<'py> {
     names: Vec<String>,
-    columns: Vec<NumpyColumn<'py>>,
+    columns: Vec<SourceColumn<'py>>,
+}
+
+impl<'py> NumpyDataSource<'py> {
+    fn new(names: Vec<String>, columns: Vec<NumpyColumn<'py>>) -> Self {
+        let columns = columns
+            .into_iter()
+            .map(|column| SourceColumn {
+                column,
+                len_some: OnceCell::new(),
+            })
+            .collect();
+        NumpyDataSource { names, columns }
+    }
 }
 
 impl<'py> data::Source for NumpyDataSource<'py> {
@@ -247,6 +779,57 @@ impl<'py> data::Source for NumpyDataSource<'py> {
     }
 }
 
+/// Owned copy of a data source, that can be shared between threads.
+/// The numpy data sources borrow python objects, and can only be read with the GIL held.
+#[derive(Debug)]
+pub struct SharedDataSource {
+    names: Vec<String>,
+    columns: Vec<data::VecColumn>,
+}
+
+impl SharedDataSource {
+    pub fn copy_from(src: &dyn data::Source) -> Self {
+        let mut names = Vec::new();
+        let mut columns = Vec::new();
+        for name in src.names() {
+            if let Some(col) = src.column(name) {
+                names.push(name.to_string());
+                columns.push(copy_column(col));
+            }
+        }
+        SharedDataSource { names, columns }
+    }
+}
+
+impl data::Source for SharedDataSource {
+    fn names(&self) -> Vec<&str> {
+        self.names.iter().map(|s| s.as_str()).collect()
+    }
+
+    fn column(&self, name: &str) -> Option<&dyn data::Column> {
+        let index = self.names.iter().position(|n| n == name)?;
+        self.columns.get(index).map(|c| c as &dyn data::Column)
+    }
+}
+
+/// Copy a column to an owned column of the same type.
+/// Missing f64 values are copied as NaN, as in `data::Column::boxed_copy`.
+fn copy_column(col: &dyn data::Column) -> data::VecColumn {
+    if let Some(col) = col.f64() {
+        data::VecColumn::F64(col.f64_iter().map(|v| v.unwrap_or(f64::NAN)).collect())
+    } else if let Some(col) = col.i64() {
+        data::VecColumn::I64(col.i64_iter().collect())
+    } else if let Some(col) = col.str() {
+        data::VecColumn::Str(col.str_iter().map(|s| s.map(str::to_string)).collect())
+    } else if let Some(col) = col.time() {
+        data::VecColumn::Time(col.time_iter().collect())
+    } else if let Some(col) = col.time_delta() {
+        data::VecColumn::TimeDelta(col.time_delta_iter().collect())
+    } else {
+        data::VecColumn::F64(vec![f64::NAN; col.len()])
+    }
+}
+
 fn extract_dict_data_source<'py>(dict: Bound<'py, PyDict>) -> PyResult<NumpyDataSource<'py>> {
     let np = dict.py().import("numpy")?;
     let float64_dtype = np.getattr("float64")?;
@@ -268,15 +851,159 @@ fn extract_dict_data_source<'py>(dict: Bound<'py, PyDict>) -> PyResult<NumpyData
             name
         )));
     }
-    Ok(NumpyDataSource { names, columns })
+    Ok(NumpyDataSource::new(names, columns))
+}
+
+/// Each column of a 2D array is a data column, named after its index ("0", "1", ...).
+fn extract_array_data_source<'py>(
+    array: Bound<'py, numpy::PyUntypedArray>,
+) -> PyResult<NumpyDataSource<'py>> {
+    use numpy::PyUntypedArrayMethods;
+
+    let shape = array.shape().to_vec();
+    if shape.len() != 2 {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Array data source must have 2 dimensions, got {}.",
+            shape.len()
+        )));
+    }
+
+    let np = array.py().import("numpy")?;
+    let float64_dtype = np.getattr("float64")?;
+    let full = pyo3::types::PySlice::full(array.py());
+
+    let names: Vec<String> = (0..shape[1]).map(|c| c.to_string()).collect();
+    let mut columns = Vec::with_capacity(names.len());
+    for (c, name) in names.iter().enumerate() {
+        // indexing yields a strided 1D view of the column, whatever the memory order
+        let col = array.get_item((&full, c))?;
+        if let Some(col) = extract_column(&col) {
+            columns.push(col);
+            continue;
+        }
+        if let Some(col) = convert_column(&col, &np, &float64_dtype) {
+            columns.push(col);
+            continue;
+        }
+        return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Column '{}' could not be converted to a numeric array.",
+            name
+        )));
+    }
+    Ok(NumpyDataSource::new(names, columns))
+}
+
+/// A list of columns (lists or arrays) gives columns named after their index ("0", "1", ...).
+/// A flat list of values gives a single column named "0".
+fn extract_list_data_source<'py>(list: Bound<'py, PyList>) -> PyResult<NumpyDataSource<'py>> {
+    let np = list.py().import("numpy")?;
+    let float64_dtype = np.getattr("float64")?;
+
+    let nested = !list.is_empty()
+        && list.iter().all(|item| {
+            item.is_instance_of::<PyList>() || item.is_instance_of::<numpy::PyUntypedArray>()
+        });
+    let items: Vec<Bound<'py, PyAny>> = if nested {
+        list.iter().collect()
+    } else {
+        vec![list.into_any()]
+    };
+
+    let len = items.first().map(|col| col.len()).transpose()?;
+    let mut names = Vec::with_capacity(items.len());
+    let mut columns = Vec::with_capacity(items.len());
+    for (c, col) in items.iter().enumerate() {
+        let name = c.to_string();
+        if Some(col.len()?) != len {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Column '{}' has {} values, but column '0' has {}: columns must have the same length.",
+                name,
+                col.len()?,
+                len.unwrap_or(0)
+            )));
+        }
+        let Some(column) = extract_column(col).or_else(|| convert_column(col, &np, &float64_dtype))
+        else {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Column '{}' could not be converted to a numeric array.",
+                name
+            )));
+        };
+        names.push(name);
+        columns.push(column);
+    }
+    Ok(NumpyDataSource::new(names, columns))
+}
+
+/// Pivot a list of records (e.g. `[{"x": 1, "y": 2}, ...]`) into columns.
+/// Columns are the union of the record keys, and missing values are None.
+/// Like for dict sources, a column is numeric, or made of strings (e.g. categories).
+fn extract_records_data_source<'py>(records: Bound<'py, PyList>) -> PyResult<NumpyDataSource<'py>> {
+    let py = records.py();
+    let mut names: Vec<String> = Vec::new();
+    let mut values: Vec<Vec<Bound<'py, PyAny>>> = Vec::new();
+    for (row, record) in records.iter().enumerate() {
+        let record = record.cast::<PyDict>()?;
+        for (key, value) in record.iter() {
+            let Ok(key) = key.cast::<PyString>() else {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Record keys must be strings, got '{}'.",
+                    key.get_type().name()?
+                )));
+            };
+            let key = key.to_str()?;
+            let index = match names.iter().position(|n| n == key) {
+                Some(index) => index,
+                None => {
+                    names.push(key.to_owned());
+                    values.push(vec![py.None().into_bound(py); row]);
+                    names.len() - 1
+                }
+            };
+            values[index].push(value);
+        }
+        // fill the columns that this record is missing
+        for col in values.iter_mut() {
+            col.resize(row + 1, py.None().into_bound(py));
+        }
+    }
+
+    let mut columns = Vec::with_capacity(names.len());
+    for (name, col) in names.iter().zip(values) {
+        let numeric = col
+            .iter()
+            .map(|v| {
+                if v.is_none() {
+                    Some(f64::NAN)
+                } else {
+                    v.extract::<f64>().ok()
+                }
+            })
+            .collect::<Option<Vec<f64>>>();
+        if let Some(numeric) = numeric {
+            columns.push(NumpyColumn::F64(
+                numpy::PyArray1::from_vec(py, numeric).readonly(),
+            ));
+            continue;
+        }
+        if let Some(strings) = extract_str_column(PyList::new(py, col)?.as_any()) {
+            columns.push(NumpyColumn::Str(strings));
+            continue;
+        }
+        return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Column '{}' could not be converted to a numeric array.",
+            name
+        )));
+    }
+    Ok(NumpyDataSource::new(names, columns))
 }
 
 fn extract_pandas_data_source<'py>(df: Bound<'py, PyAny>) -> PyResult<NumpyDataSource<'py>> {
     let np = df.py().import("numpy")?;
     let float64_dtype = np.getattr("float64")?;
 
-    let names: Vec<String> = df.getattr("columns")?.extract()?;
-    let mut columns = Vec::with_capacity(names.len());
+    let mut names: Vec<String> = df.getattr("columns")?.extract()?;
+    let mut columns = Vec::with_capacity(names.len() + 1);
     for name in &names {
         let col = df.get_item(name)?;
         if let Some(array) = extract_column(&col) {
@@ -292,41 +1019,431 @@ fn extract_pandas_data_source<'py>(df: Bound<'py, PyAny>) -> PyResult<NumpyDataS
             name
         )));
     }
-    Ok(NumpyDataSource { names, columns })
+
+    // The index is exposed as a virtual column, named after the index or "index".
+    // A real column with the same name takes precedence.
+    let index = df.getattr("index")?;
+    let index_name = match getattr_not_none(&index, "name")? {
+        Some(name) => name.str()?.to_str()?.to_owned(),
+        None => "index".to_owned(),
+    };
+    if names.contains(&index_name) {
+        // a default range index is not worth a warning (e.g. after reset_index())
+        if extract_class_name(&index)? != "RangeIndex" {
+            let py = df.py();
+            let msg = format!(
+                "DataFrame index is shadowed by column '{}' and is not available for plotting.",
+                index_name
+            );
+            PyErr::warn(
+                py,
+                &py.get_type::<pyo3::exceptions::PyUserWarning>(),
+                &std::ffi::CString::new(msg)?,
+                1,
+            )?;
+        }
+    } else if let Some(col) = extract_column(&index).or_else(|| {
+        // tz-aware datetime indexes are only read as local times from the index itself
+        let array = index.call_method0("to_numpy").ok()?;
+        extract_column(&array).or_else(|| convert_column(&array, &np, &float64_dtype))
+    }) {
+        names.push(index_name);
+        columns.push(col);
+    }
+
+    Ok(NumpyDataSource::new(names, columns))
+}
+
+fn extract_polars_data_source<'py>(df: Bound<'py, PyAny>) -> PyResult<NumpyDataSource<'py>> {
+    let np = df.py().import("numpy")?;
+    let float64_dtype = np.getattr("float64")?;
+    let pl = df.py().import("polars")?;
+    let pl_float64 = pl.getattr("Float64")?;
+    let pl_str_dtypes = [
+        pl.getattr("String")?,
+        pl.getattr("Categorical")?,
+        pl.getattr("Enum")?,
+    ];
+
+    let names: Vec<String> = df.getattr("columns")?.extract()?;
+    let mut columns = Vec::with_capacity(names.len());
+    for name in &names {
+        let series = df.call_method1("get_column", (name,))?;
+        let dtype = series.getattr("dtype")?;
+        let col = if pl_str_dtypes.iter().any(|t| dtype.eq(t).unwrap_or(false)) {
+            // nulls come out of to_list as None
+            let values = series.call_method0("to_list")?;
+            extract_str_column(&values).map(NumpyColumn::Str)
+        } else if dtype.call_method0("is_temporal")?.extract()? {
+            // to_numpy gives datetime64 arrays, where nulls are NaT
+            let array = series.call_method0("to_numpy")?;
+            extract_column(&array).or_else(|| convert_column(&array, &np, &float64_dtype))
+        } else {
+            let mut series = series;
+            // NaN is the null sentinel of the column iterators, and i64 arrays can't hold it,
+            // so numeric columns with nulls are converted to f64 first.
+            let null_count: usize = series.call_method0("null_count")?.extract()?;
+            if null_count > 0 && dtype.call_method0("is_numeric")?.extract()? {
+                series = series
+                    .call_method1("cast", (pl_float64.clone(),))?
+                    .call_method1("fill_null", (f64::NAN,))?;
+            }
+            let array = series.call_method0("to_numpy")?;
+            extract_column(&array).or_else(|| convert_column(&array, &np, &float64_dtype))
+        };
+        let Some(col) = col else {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Column '{}' could not be converted to a numeric array.",
+                name
+            )));
+        };
+        columns.push(col);
+    }
+    Ok(NumpyDataSource::new(names, columns))
+}
+
+fn extract_arrow_data_source<'py>(table: Bound<'py, PyAny>) -> PyResult<NumpyDataSource<'py>> {
+    let pa = table.py().import("pyarrow")?;
+    let pa_types = pa.getattr("types")?;
+    let int64_type = pa.call_method0("int64")?;
+    let float64_type = pa.call_method0("float64")?;
+
+    let names: Vec<String> = table.getattr("column_names")?.extract()?;
+    let mut columns = Vec::with_capacity(names.len());
+    for name in &names {
+        let chunked = table.call_method1("column", (name,))?;
+        let arrow_type = chunked.getattr("type")?;
+        let conversion_err = || {
+            pyo3::exceptions::PyTypeError::new_err(format!(
+                "Column '{}' of arrow type '{}' could not be converted to a numeric array.",
+                name, arrow_type
+            ))
+        };
+
+        let is_int: bool = pa_types
+            .call_method1("is_integer", (&arrow_type,))?
+            .extract()?;
+        let is_float: bool = pa_types
+            .call_method1("is_floating", (&arrow_type,))?
+            .extract()?;
+        if !is_int && !is_float {
+            return Err(conversion_err());
+        }
+
+        // NaN is the null sentinel of the column iterators, and i64 arrays can't hold it,
+        // so columns with nulls are converted to f64.
+        let null_count: usize = chunked.getattr("null_count")?.extract()?;
+        let target_type = if is_int && null_count == 0 {
+            &int64_type
+        } else {
+            &float64_type
+        };
+        let mut chunked = chunked
+            .call_method1("cast", (target_type,))
+            .map_err(|_| conversion_err())?;
+        if null_count > 0 {
+            chunked = chunked.call_method1("fill_null", (f64::NAN,))?;
+        }
+
+        // to_numpy concatenates the chunks into a contiguous array
+        let array = chunked.call_method0("to_numpy")?;
+        let Some(array) = extract_column(&array) else {
+            return Err(conversion_err());
+        };
+        columns.push(array);
+    }
+    Ok(NumpyDataSource::new(names, columns))
 }
 
 fn extract_column<'py>(
     col: &Bound<'py, PyAny>,
 ) -> Option<NumpyColumn<'py>> {
-    if let Some(array) = is_numpy_array_f64(col) {
+    if let Some(array) = is_numpy_masked_array(col) {
+        Some(NumpyColumn::F64(array))
+    } else if let Some(array) = is_numpy_array_f64(col) {
         Some(NumpyColumn::F64(array))
     } else if let Some(array) = is_numpy_array_f32(col) {
         Some(NumpyColumn::F32(array))
     } else if let Some(array) = is_numpy_array_i64(col) {
         Some(NumpyColumn::I64(array))
+    } else if let Some(array) = is_numpy_array_i32(col) {
+        Some(NumpyColumn::I32(array))
+    } else if let Some(array) = is_numpy_array_u8(col) {
+        Some(NumpyColumn::U8(array))
+    } else if let Some(array) = is_numpy_array_bool(col) {
+        Some(NumpyColumn::Bool(array))
+    } else if let Some((values, unit_ns)) = is_numpy_array_datetime(col) {
+        Some(NumpyColumn::DateTime { values, unit_ns })
     } else {
-        None
+        extract_lazy_column(col).map(NumpyColumn::Lazy)
+    }
+}
+
+/// Extract a `plotive.LazyColumn`, reading its values by chunks from its source.
+fn extract_lazy_column<'py>(col: &Bound<'py, PyAny>) -> Option<LazyColumn<'py>> {
+    if extract_class_name(col).ok()? != "LazyColumn" {
+        return None;
+    }
+    let source = col.getattr("source").ok()?;
+    let len = source.len().ok()?;
+    let chunk_size: usize = col.getattr("chunk_size").ok()?.extract().ok()?;
+    let chunk_size = chunk_size.max(1);
+    let chunks = (0..len)
+        .step_by(chunk_size)
+        .map(|start| (start, (start + chunk_size).min(len)))
+        .collect();
+    Some(LazyColumn {
+        source,
+        len,
+        chunks,
+        stats: Cell::new(None),
+        failed: Cell::new(false),
+    })
+}
+
+/// Columns converted by `convert_column`, reused across renders. `None` if the cache is disabled.
+static COLUMN_CACHE: Mutex<ColumnCache> = Mutex::new(None);
+
+/// Maximum number of cached columns. The cache is cleared when it is full.
+const COLUMN_CACHE_CAPACITY: usize = 64;
+
+/// Identifies the numpy buffer of a column.
+/// The pointer alone is not enough, as views of the same buffer share it.
+#[derive(PartialEq, Eq, Hash)]
+struct ColumnKey {
+    ptr: usize,
+    typestr: String,
+    shape: Vec<usize>,
+    strides: Option<Vec<isize>>,
+}
+
+struct CachedColumn {
+    /// Keeps the source array alive, so that its buffer address is not reused while cached.
+    /// This is the array the key was read from, not the column, which may reallocate it.
+    _source: Py<PyAny>,
+    values: CachedValues,
+}
+
+enum CachedValues {
+    F64(Py<numpy::PyArray1<f64>>),
+    Str(Vec<Option<String>>),
+}
+
+type ColumnCache = Option<HashMap<ColumnKey, CachedColumn>>;
+
+/// Lock the column cache.
+/// Conversions may release the GIL, so the lock must be taken without holding it.
+fn lock_column_cache(py: Python<'_>) -> MutexGuard<'static, ColumnCache> {
+    COLUMN_CACHE
+        .lock_py_attached(py)
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn set_column_cache(py: Python<'_>, enabled: bool) {
+    let mut cache = lock_column_cache(py);
+    if enabled {
+        cache.get_or_insert_with(HashMap::new);
+    } else {
+        *cache = None;
+    }
+}
+
+pub fn clear_column_cache(py: Python<'_>) {
+    if let Some(cache) = lock_column_cache(py).as_mut() {
+        cache.clear();
     }
 }
 
+/// The numpy array backing a column, and its key in the cache.
+/// Returns `None` if the column is not backed by a numpy array
+/// (e.g. lists, categorical or nullable integer columns), in which case it is not cached.
+fn column_buffer<'py>(
+    col: &Bound<'py, PyAny>,
+) -> Option<(ColumnKey, Bound<'py, numpy::PyUntypedArray>)> {
+    // pandas series are keyed on their underlying numpy array
+    let array = if col.cast::<numpy::PyUntypedArray>().is_ok() {
+        col.clone()
+    } else {
+        col.getattr("values").ok()?
+    };
+    let array = array.cast_into::<numpy::PyUntypedArray>().ok()?;
+    let interface = array.getattr("__array_interface__").ok()?;
+    let (ptr, _readonly): (usize, bool) = interface.get_item("data").ok()?.extract().ok()?;
+    let key = ColumnKey {
+        ptr,
+        typestr: interface.get_item("typestr").ok()?.extract().ok()?,
+        shape: interface.get_item("shape").ok()?.extract().ok()?,
+        strides: interface.get_item("strides").ok()?.extract().ok()?,
+    };
+    Some((key, array))
+}
+
+fn cached_values<'py>(py: Python<'py>, values: &CachedValues) -> NumpyColumn<'py> {
+    match values {
+        CachedValues::F64(array) => NumpyColumn::F64(array.bind(py).readonly()),
+        CachedValues::Str(values) => NumpyColumn::Str(values.clone()),
+    }
+}
+
+/// Convert a column that is not a numpy array of a supported type.
+/// If the column cache is enabled, conversions of numpy-backed columns are reused.
 fn convert_column<'py>(
     col: &Bound<'py, PyAny>,
     np: &Bound<'py, PyAny>,
     float64_dtype: &Bound<'py, PyAny>,
 ) -> Option<NumpyColumn<'py>> {
-    if let Ok(data) = col.call_method1("astype", (float64_dtype.clone(),)) {
-        if let Ok(values) = data.getattr("values") {
-            if let Ok(array) = values.cast::<numpy::PyArray1<f64>>() {
-                return Some(NumpyColumn::F64(array.readonly()));
-            }
-        }
+    let py = col.py();
+    let mut cache = lock_column_cache(py);
+    let Some(cache) = cache.as_mut() else {
+        return convert_column_uncached(col, np, float64_dtype);
+    };
+    let Some((key, array)) = column_buffer(col) else {
+        return convert_column_uncached(col, np, float64_dtype);
+    };
+    if let Some(cached) = cache.get(&key) {
+        return Some(cached_values(py, &cached.values));
     }
-    if let Ok(list) = col.extract::<Vec<f64>>() {
-        if let Ok(array) = np.call_method1("array", (list,)) {
-            if let Ok(array) = array.cast::<numpy::PyArray1<f64>>() {
-                return Some(NumpyColumn::F64(array.readonly()));
-            }
+    let column = convert_column_uncached(col, np, float64_dtype)?;
+    let values = match &column {
+        NumpyColumn::F64(values) => CachedValues::F64(values.as_unbound().clone_ref(py)),
+        NumpyColumn::Str(values) => CachedValues::Str(values.clone()),
+        _ => return Some(column),
+    };
+    if cache.len() >= COLUMN_CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(
+        key,
+        CachedColumn {
+            _source: array.into_any().unbind(),
+            values,
+        },
+    );
+    Some(column)
+}
+
+fn convert_column_uncached<'py>(
+    col: &Bound<'py, PyAny>,
+    np: &Bound<'py, PyAny>,
+    float64_dtype: &Bound<'py, PyAny>,
+) -> Option<NumpyColumn<'py>> {
+    // astype would convert the categories (or fail), not the labels
+    if let Some(values) = extract_categorical_column(col) {
+        return Some(NumpyColumn::Str(values));
+    }
+    // astype would fail on NA
+    if let Some((values, mask)) = extract_nullable_int_column(col) {
+        return Some(NumpyColumn::NullableI64 { values, mask });
+    }
+    if let Ok(data) = col.call_method1("astype", (float64_dtype.clone(),))
+        && let Ok(values) = data.getattr("values")
+        && let Ok(array) = values.cast::<numpy::PyArray1<f64>>()
+    {
+        return Some(NumpyColumn::F64(array.readonly()));
+    }
+    if let Ok(list) = col.extract::<Vec<f64>>()
+        && let Ok(array) = np.call_method1("array", (list,))
+        && let Ok(array) = array.cast::<numpy::PyArray1<f64>>()
+    {
+        return Some(NumpyColumn::F64(array.readonly()));
+    }
+    extract_str_column(col).map(NumpyColumn::Str)
+}
+
+/// Extract the values and NA mask of a pandas nullable integer series (e.g. `Int64`).
+fn extract_nullable_int_column<'py>(
+    col: &Bound<'py, PyAny>,
+) -> Option<(
+    numpy::borrow::PyReadonlyArray1<'py, i64>,
+    numpy::borrow::PyReadonlyArray1<'py, bool>,
+)> {
+    let dtype_name: String = col
+        .getattr("dtype")
+        .ok()?
+        .getattr("name")
+        .ok()?
+        .extract()
+        .ok()?;
+    if !matches!(
+        dtype_name.as_str(),
+        "Int8" | "Int16" | "Int32" | "Int64" | "UInt8" | "UInt16" | "UInt32" | "UInt64"
+    ) {
+        return None;
+    }
+    let kwargs = PyDict::new(col.py());
+    kwargs.set_item("dtype", "int64").ok()?;
+    kwargs.set_item("na_value", 0).ok()?;
+    let values = col.call_method("to_numpy", (), Some(&kwargs)).ok()?;
+    let values = values.cast::<numpy::PyArray1<i64>>().ok()?.readonly();
+    let mask = col
+        .call_method0("isna")
+        .ok()?
+        .call_method0("to_numpy")
+        .ok()?;
+    let mask = mask.cast::<numpy::PyArray1<bool>>().ok()?.readonly();
+    Some((values, mask))
+}
+
+/// Extract the labels of a pandas categorical series.
+/// Missing values (code -1) are None.
+fn extract_categorical_column(col: &Bound<'_, PyAny>) -> Option<Vec<Option<String>>> {
+    let dtype_name: String = col
+        .getattr("dtype")
+        .ok()?
+        .getattr("name")
+        .ok()?
+        .extract()
+        .ok()?;
+    if dtype_name != "category" {
+        return None;
+    }
+    let cat = col.getattr("cat").ok()?;
+    let categories = cat
+        .getattr("categories")
+        .ok()?
+        .call_method0("tolist")
+        .ok()?;
+    let categories = categories.cast::<PyList>().ok()?;
+    let categories = categories
+        .iter()
+        .map(|c| Some(c.str().ok()?.to_str().ok()?.to_owned()))
+        .collect::<Option<Vec<String>>>()?;
+    let codes: Vec<i64> = cat
+        .getattr("codes")
+        .ok()?
+        .call_method0("tolist")
+        .ok()?
+        .extract()
+        .ok()?;
+    let values = codes
+        .into_iter()
+        .map(|code| {
+            usize::try_from(code)
+                .ok()
+                .and_then(|c| categories.get(c).cloned())
+        })
+        .collect();
+    Some(values)
+}
+
+/// Extract a column of strings, where None and NaN are missing values.
+/// Works for lists, numpy string or object arrays and pandas series.
+fn extract_str_column(col: &Bound<'_, PyAny>) -> Option<Vec<Option<String>>> {
+    let items = if col.hasattr("tolist").ok()? {
+        col.call_method0("tolist").ok()?
+    } else {
+        col.clone()
+    };
+    let items = items.cast::<PyList>().ok()?;
+    let mut values = Vec::with_capacity(items.len());
+    for item in items.iter() {
+        if let Ok(s) = item.cast::<PyString>() {
+            values.push(Some(s.to_str().ok()?.to_owned()));
+        } else if item.is_none() || item.extract::<f64>().is_ok_and(|v| v.is_nan()) {
+            values.push(None);
+        } else {
+            return None;
         }
     }
-    None
+    Some(values)
 }
@@ -1,22 +1,29 @@
-use plotive::{des, geom, style};
+use std::collections::HashMap;
+
+use plotive::{data, des, geom, style};
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 
-use crate::{py_annot::extract_annot, py_style::{extract_series_color, extract_stroke_pattern, extract_theme_color, extract_theme_stroke}};
+use crate::{py_annot::extract_annot, py_style::{extract_marker_shape, extract_opacity, extract_series_color, extract_stroke_pattern, extract_theme_color, extract_theme_stroke}};
 
-use super::{extract_class_name, getattr_not_none};
+use super::decimate;
+use super::{
+    ErrPath, describe_value, extract_class_name, extract_f32_like, extract_f64_like,
+    getattr_not_none,
+};
 
 fn extract_padding(py_padding: &Bound<'_, PyAny>) -> PyResult<geom::Padding> {
-    if let Ok(pad) = py_padding.extract::<f32>() {
+    if let Ok(pad) = extract_f32_like(py_padding) {
         Ok(geom::Padding::Even(pad))
     } else if let Ok((h, v)) = py_padding.extract::<(f32, f32)>() {
         Ok(geom::Padding::Center { v, h })
     } else if let Ok((t, r, b, l)) = py_padding.extract::<(f32, f32, f32, f32)>() {
         Ok(geom::Padding::Custom { t, r, b, l })
     } else {
-        Err(pyo3::exceptions::PyTypeError::new_err(
-            "Padding must be a float, a tuple of two floats, or a tuple of four floats.",
-        ))
+        Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Padding must be a float, a tuple of two floats, or a tuple of four floats. Got {}.",
+            describe_value(py_padding)
+        )))
     }
 }
 
@@ -28,9 +35,10 @@ fn extract_data_col(col: &Bound<'_, PyAny>) -> PyResult<des::DataCol> {
     } else if let Ok(values) = col.extract::<Vec<String>>() {
         Ok(des::DataCol::Inline(values.into()))
     } else {
-        Err(pyo3::exceptions::PyTypeError::new_err(
-            "DataCol must be either a string (source reference) or a list of values.",
-        ))
+        Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "DataCol must be either a string (source reference) or a list of values. Got {}.",
+            describe_value(col)
+        )))
     }
 }
 
@@ -40,85 +48,660 @@ fn extract_axis_ref(rf: &Bound<'_, PyAny>) -> PyResult<des::axis::Ref> {
     } else if let Ok(idx) = rf.extract::<usize>() {
         Ok(des::axis::Ref::Idx(idx))
     } else {
-        Err(pyo3::exceptions::PyTypeError::new_err(
-            "Axis reference must be either a string (axis id or title) or an integer (axis index).",
-        ))
+        Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Axis reference must be either a string (axis id or title) or an integer (axis index). Got {}.",
+            describe_value(rf)
+        )))
     }
 }
 
-fn extract_series(ser: &Bound<'_, PyAny>) -> PyResult<des::Series> {
+fn extract_series(ser: &Bound<'_, PyAny>, data_src: &dyn data::Source) -> PyResult<des::Series> {
     // check subtype of series
     let cls_name = extract_class_name(ser)?;
+    check_inline_lengths(ser, &cls_name)?;
+    check_src_refs(ser, &cls_name, data_src)?;
     let series = match cls_name.as_str() {
-        "Line" => {
-            let x = ser.getattr("x")?;
-            let y = ser.getattr("y")?;
-            let x_data = extract_data_col(&x)?;
-            let y_data = extract_data_col(&y)?;
-
-            let mut line = des::series::Line::new(x_data, y_data);
-            if let Some(name) = getattr_not_none(ser, "name")? {
-                let name_str: String = name.extract()?;
-                line = line.with_name(name_str);
+        "Line" => des::Series::Line(extract_line_series(ser, data_src)?),
+        "Step" => des::Series::Line(extract_step_series(ser, data_src)?),
+        "Scatter" => des::Series::Scatter(extract_scatter_series(ser)?),
+        "HBar" => des::Series::BarsGroup(extract_hbar_series(ser, data_src)?),
+        "Histogram" => des::Series::Histogram(extract_histogram_series(ser, data_src)?),
+        _ => {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Unsupported series type: {}",
+                cls_name
+            )));
+        }
+    };
+
+    Ok(series)
+}
+
+/// Check that the inline x and y data columns of a series have the same length.
+/// Source references are only resolved at render time and are not checked here.
+fn check_inline_lengths(ser: &Bound<'_, PyAny>, cls_name: &str) -> PyResult<()> {
+    // histograms have a single column, and scatter sizes and colors are not per-point
+    let attrs: &[&str] = match cls_name {
+        "Line" | "Step" | "Scatter" | "HBar" => &["x", "y"],
+        _ => return Ok(()),
+    };
+    let mut first: Option<(&str, usize)> = None;
+    for attr in attrs {
+        let Some(col) = getattr_not_none(ser, attr)? else {
+            continue;
+        };
+        let Some(len) = inline_col_len(&col) else {
+            continue;
+        };
+        match first {
+            None => first = Some((attr, len)),
+            Some((first_attr, first_len)) if first_len != len => {
+                let series = match getattr_not_none(ser, "name")? {
+                    Some(name) => format!("{} series '{}'", cls_name, name),
+                    None => format!("{} series", cls_name),
+                };
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{}: length of '{}' ({}) does not match the length of '{}' ({}).",
+                    series, attr, len, first_attr, first_len
+                )));
             }
-            if let Some(py_x_axis) = getattr_not_none(ser, "x_axis")? {
-                let x_axis = extract_axis_ref(&py_x_axis)?;
-                line = line.with_x_axis(x_axis);
+            Some(..) => (),
+        }
+    }
+    Ok(())
+}
+
+/// Length of an inline data column, or `None` for a source reference.
+fn inline_col_len(col: &Bound<'_, PyAny>) -> Option<usize> {
+    if col.is_instance_of::<pyo3::types::PyString>() {
+        None
+    } else {
+        col.len().ok()
+    }
+}
+
+/// Check that the columns referenced by name by a series exist in the data source.
+fn check_src_refs(
+    ser: &Bound<'_, PyAny>,
+    cls_name: &str,
+    data_src: &dyn data::Source,
+) -> PyResult<()> {
+    let attrs: &[&str] = match cls_name {
+        "Line" | "Step" | "Scatter" | "HBar" => &["x", "y"],
+        "Histogram" => &["values"],
+        _ => &[],
+    };
+    for attr in attrs {
+        let Some(col) = getattr_not_none(ser, attr)? else {
+            continue;
+        };
+        let Ok(name) = col.extract::<&str>() else {
+            continue;
+        };
+        if data_src.column(name).is_some() {
+            continue;
+        }
+        let names = data_src.names();
+        let msg = if names.is_empty() {
+            format!(
+                "Column '{}' referenced by '{}' not found: no data source was provided.",
+                name, attr
+            )
+        } else {
+            format!(
+                "Column '{}' referenced by '{}' not found in data source. Available columns: {}",
+                name,
+                attr,
+                names.join(", ")
+            )
+        };
+        return Err(pyo3::exceptions::PyKeyError::new_err(msg));
+    }
+    Ok(())
+}
+
+/// Whether the series is listed in the legend.
+/// plotive lists all the named series, so hidden series are left unnamed.
+fn is_series_in_legend(ser: &Bound<'_, PyAny>) -> PyResult<bool> {
+    match getattr_not_none(ser, "show_in_legend")? {
+        Some(py_show) => py_show.extract(),
+        None => Ok(true),
+    }
+}
+
+/// Build an inline column `0, 1, ..., n-1` matching the length of the `y` column.
+/// Used when a line is given only its y values.
+fn extract_index_col(y: &Bound<'_, PyAny>, data_src: &dyn data::Source) -> PyResult<des::DataCol> {
+    let len = if let Ok(src_ref) = y.extract::<String>() {
+        let Some(col) = data_src.column(&src_ref) else {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Cannot generate x indices: column '{}' not found in data source.",
+                src_ref
+            )));
+        };
+        col.len()
+    } else {
+        y.len()?
+    };
+    let indices: Vec<f64> = (0..len).map(|i| i as f64).collect();
+    Ok(des::DataCol::Inline(indices.into()))
+}
+
+/// Resolve the values of a numeric column, given inline or as a data source reference.
+/// Returns `None` if the column is not numeric (e.g. strings or times) or not found.
+fn resolve_f64_col(
+    col: &Bound<'_, PyAny>,
+    data_src: &dyn data::Source,
+) -> Option<Vec<Option<f64>>> {
+    if let Ok(src_ref) = col.extract::<String>() {
+        let col = data_src.column(&src_ref)?;
+        if let Some(col) = col.f64() {
+            Some(col.f64_iter().collect())
+        } else {
+            col.i64()
+                .map(|col| col.i64_iter().map(|v| v.map(|v| v as f64)).collect())
+        }
+    } else {
+        let values: Vec<f64> = col.extract().ok()?;
+        Some(values.into_iter().map(Some).collect())
+    }
+}
+
+/// Decimate the line to `max_points` points if it is longer.
+/// Lines with non-numeric columns are left untouched.
+fn decimate_line(
+    x: Option<&Bound<'_, PyAny>>,
+    y: &Bound<'_, PyAny>,
+    max_points: usize,
+    data_src: &dyn data::Source,
+) -> PyResult<Option<(des::DataCol, des::DataCol)>> {
+    let Some(y_values) = resolve_f64_col(y, data_src) else {
+        return Ok(None);
+    };
+    let x_values = match x {
+        Some(x) => {
+            let Some(x_values) = resolve_f64_col(x, data_src) else {
+                return Ok(None);
+            };
+            x_values
+        }
+        None => (0..y_values.len()).map(|i| Some(i as f64)).collect(),
+    };
+    if x_values.len() != y_values.len() {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Cannot decimate a line with {} x values and {} y values.",
+            x_values.len(),
+            y_values.len()
+        )));
+    }
+    let Some((x, y)) = decimate::min_max(&x_values, &y_values, max_points) else {
+        return Ok(None);
+    };
+    Ok(Some((
+        des::DataCol::Inline(x.into()),
+        des::DataCol::Inline(y.into()),
+    )))
+}
+
+fn extract_line_series(
+    ser: &Bound<'_, PyAny>,
+    data_src: &dyn data::Source,
+) -> PyResult<des::series::Line> {
+    let y = ser.getattr("y")?;
+    let x = getattr_not_none(ser, "x")?;
+    let mut x_data = if let Some(x) = &x {
+        extract_data_col(x)?
+    } else {
+        extract_index_col(&y, data_src)?
+    };
+    let mut y_data = extract_data_col(&y)?;
+
+    // without explicit name, a line referencing a source column is named after it
+    let name = if !is_series_in_legend(ser)? {
+        None
+    } else if let Some(name) = getattr_not_none(ser, "name")? {
+        Some(name.extract::<String>()?)
+    } else if let des::DataCol::SrcRef(src_ref) = &y_data {
+        Some(src_ref.clone())
+    } else {
+        None
+    };
+
+    if let Some(py_max_points) = getattr_not_none(ser, "max_points")? {
+        let max_points: usize = py_max_points.extract()?;
+        if max_points < 4 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "max_points must be at least 4. Got {}.",
+                max_points
+            )));
+        }
+        if let Some((x, y)) = decimate_line(x.as_ref(), &y, max_points, data_src)? {
+            x_data = x;
+            y_data = y;
+        }
+    }
+
+    let mut line = des::series::Line::new(x_data, y_data);
+    if let Some(name) = name {
+        line = line.with_name(name);
+    }
+    if let Some(py_x_axis) = getattr_not_none(ser, "x_axis")? {
+        let x_axis = extract_axis_ref(&py_x_axis)?;
+        line = line.with_x_axis(x_axis);
+    }
+    if let Some(py_y_axis) = getattr_not_none(ser, "y_axis")? {
+        let y_axis = extract_axis_ref(&py_y_axis)?;
+        line = line.with_y_axis(y_axis);
+    }
+    let py_width = ser.getattr("linewidth")?;
+    let py_style = ser.getattr("linestyle")?;
+    let py_color = ser.getattr("color")?;
+    if !py_width.is_none() || !py_style.is_none() || !py_color.is_none() {
+        let mut stroke = style::series::Stroke::default();
+        if !py_width.is_none() {
+            stroke.width = extract_f32_like(&py_width)?;
+        }
+        if !py_style.is_none() {
+            stroke.pattern = extract_stroke_pattern(&py_style)?;
+        }
+        if !py_color.is_none() {
+            stroke.color = extract_series_color(&py_color)?;
+        }
+        line = line.with_line(stroke);
+    }
+
+    if let Some(py_interp) = getattr_not_none(ser, "interpolation")? {
+        let interp_str: &str = py_interp.extract()?;
+        let interp = match interp_str {
+            "linear" => des::series::Interpolation::Linear,
+            "step-early" => des::series::Interpolation::StepEarly,
+            "step-middle" => des::series::Interpolation::StepMiddle,
+            "step-late" | "step" => des::series::Interpolation::StepLate,
+            "cubic" | "spline" => des::series::Interpolation::Spline,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown interpolation method: {}",
+                    interp_str
+                )));
             }
-            if let Some(py_y_axis) = getattr_not_none(ser, "y_axis")? {
-                let y_axis = extract_axis_ref(&py_y_axis)?;
-                line = line.with_y_axis(y_axis);
+        };
+        line = line.with_interpolation(interp);
+    }
+
+    Ok(line)
+}
+
+fn extract_step_series(
+    ser: &Bound<'_, PyAny>,
+    data_src: &dyn data::Source,
+) -> PyResult<des::series::Line> {
+    let interp = if let Some(py_where) = getattr_not_none(ser, "where")? {
+        let where_str: &str = py_where.extract()?;
+        match where_str {
+            "pre" => des::series::Interpolation::StepEarly,
+            "mid" => des::series::Interpolation::StepMiddle,
+            "post" => des::series::Interpolation::StepLate,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown step 'where' value: {} (expected 'pre', 'mid' or 'post')",
+                    where_str
+                )));
             }
-            let py_width = ser.getattr("linewidth")?;
-            let py_style = ser.getattr("linestyle")?;
-            let py_color = ser.getattr("color")?;
-            if !py_width.is_none() || !py_style.is_none() || !py_color.is_none() {
-                let mut stroke = style::series::Stroke::default();
-                if !py_width.is_none() {
-                    stroke.width = py_width.extract()?;
-                }
-                if !py_style.is_none() {
-                    stroke.pattern = extract_stroke_pattern(&py_style)?;
-                }
-                if !py_color.is_none() {
-                    stroke.color = extract_series_color(&py_color)?;
-                }
-                line = line.with_line(stroke);
+        }
+    } else {
+        des::series::Interpolation::StepEarly
+    };
+    Ok(extract_line_series(ser, data_src)?.with_interpolation(interp))
+}
+
+fn extract_scatter_series(ser: &Bound<'_, PyAny>) -> PyResult<des::series::Scatter> {
+    let x = ser.getattr("x")?;
+    let y = ser.getattr("y")?;
+    let x_data = extract_data_col(&x)?;
+    let y_data = extract_data_col(&y)?;
+
+    let mut scatter = des::series::Scatter::new(x_data, y_data);
+    if let Some(name) = getattr_not_none(ser, "name")?
+        && is_series_in_legend(ser)?
+    {
+        let name_str: String = name.extract()?;
+        scatter = scatter.with_name(name_str);
+    }
+    if let Some(py_x_axis) = getattr_not_none(ser, "x_axis")? {
+        let x_axis = extract_axis_ref(&py_x_axis)?;
+        scatter = scatter.with_x_axis(x_axis);
+    }
+    if let Some(py_y_axis) = getattr_not_none(ser, "y_axis")? {
+        let y_axis = extract_axis_ref(&py_y_axis)?;
+        scatter = scatter.with_y_axis(y_axis);
+    }
+
+    let py_size = getattr_not_none(ser, "size")?;
+    let py_color = getattr_not_none(ser, "color")?;
+    let py_marker = getattr_not_none(ser, "marker")?;
+    if py_marker.is_some() || py_size.is_some() || py_color.is_some() {
+        let mut marker = style::series::Marker::default();
+        if let Some(py_marker) = py_marker {
+            marker.shape = extract_marker_shape(&py_marker)?;
+        }
+        if let Some(py_size) = py_size {
+            let size = extract_f32_like(&py_size)?;
+            if size <= 0.0 {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Scatter size must be positive, got {}",
+                    size
+                )));
             }
+            marker.size = style::MarkerSize(size);
+        }
+        if let Some(py_color) = py_color {
+            marker.fill = Some(style::series::Fill::Solid {
+                color: extract_series_color(&py_color)?,
+                opacity: None,
+            });
+        }
+        scatter = scatter.with_marker(marker);
+    }
 
-            if let Some(py_interp) = getattr_not_none(ser, "interpolation")? {
-                let interp_str: &str = py_interp.extract()?;
-                let interp = match interp_str {
-                    "linear" => des::series::Interpolation::Linear,
-                    "step-early" => des::series::Interpolation::StepEarly,
-                    "step-middle" => des::series::Interpolation::StepMiddle,
-                    "step-late" | "step" => des::series::Interpolation::StepLate,
-                    "cubic" | "spline" => des::series::Interpolation::Spline,
-                    _ => {
-                        return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                            "Unknown interpolation method: {}",
-                            interp_str
-                        )));
-                    }
-                };
-                line = line.with_interpolation(interp);
+    Ok(scatter)
+}
+
+/// Check whether a data column holds strings, given inline or as a data source reference.
+fn is_str_data_col(col: &Bound<'_, PyAny>, data_src: &dyn data::Source) -> bool {
+    if let Ok(src_ref) = col.extract::<String>() {
+        data_src
+            .column(&src_ref)
+            .is_some_and(|col| col.str().is_some())
+    } else {
+        col.extract::<Vec<String>>().is_ok()
+    }
+}
+
+/// Check that an HBar series has numeric lengths along x and string categories along y.
+fn check_hbar_cols(
+    x: &Bound<'_, PyAny>,
+    y: &Bound<'_, PyAny>,
+    data_src: &dyn data::Source,
+) -> PyResult<()> {
+    if is_str_data_col(x, data_src) {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "HBar series must have numeric 'x' values: 'y' holds the bar categories and 'x' the bar lengths.",
+        ));
+    }
+    if is_str_data_col(y, data_src) {
+        return Ok(());
+    }
+    if let Ok(src_ref) = y.extract::<String>() {
+        Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "HBar series must have string 'y' categories, but column '{}' does not hold strings.",
+            src_ref
+        )))
+    } else {
+        Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "HBar series must have string 'y' categories. Got {}.",
+            describe_value(y)
+        )))
+    }
+}
+
+/// Extract the bar thickness of an HBar series, as a fraction of the category spacing.
+fn extract_hbar_height(ser: &Bound<'_, PyAny>) -> PyResult<Option<f32>> {
+    let Some(py_height) = getattr_not_none(ser, "height")? else {
+        return Ok(None);
+    };
+    let height = extract_f32_like(&py_height)?;
+    if !(height > 0.0 && height <= 1.0) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "HBar height must be in the (0, 1] interval, got {}",
+            height
+        )));
+    }
+    Ok(Some(height))
+}
+
+/// Extract the bar lengths, name and color of an HBar series.
+fn extract_hbar_bar_series(ser: &Bound<'_, PyAny>) -> PyResult<des::series::BarSeries> {
+    let x = ser.getattr("x")?;
+    let mut bars = des::series::BarSeries::new(extract_data_col(&x)?);
+    if let Some(name) = getattr_not_none(ser, "name")?
+        && is_series_in_legend(ser)?
+    {
+        let name_str: String = name.extract()?;
+        bars = bars.with_name(name_str);
+    }
+    if let Some(py_color) = getattr_not_none(ser, "color")? {
+        let color = extract_series_color(&py_color)?;
+        bars = bars.with_fill(style::series::Fill::Solid {
+            color,
+            opacity: None,
+        });
+    }
+    Ok(bars)
+}
+
+/// An HBar series is a group of a single bar series, along the categories of the y axis.
+fn extract_hbar_series(
+    ser: &Bound<'_, PyAny>,
+    data_src: &dyn data::Source,
+) -> PyResult<des::series::BarsGroup> {
+    let x = ser.getattr("x")?;
+    let y = ser.getattr("y")?;
+    check_hbar_cols(&x, &y, data_src)?;
+    let categories = extract_data_col(&y)?;
+    let bars = extract_hbar_bar_series(ser)?;
+
+    let mut arrangement = des::series::BarsAsideArrangement::default();
+    if let Some(height) = extract_hbar_height(ser)? {
+        // centered in the category bin
+        arrangement.offset = (1.0 - height) / 2.0;
+        arrangement.width = height;
+    }
+    Ok(des::series::BarsGroup::new(categories, vec![bars])
+        .with_orientation(des::series::BarsOrientation::Horizontal)
+        .with_arrangement(des::series::BarsArrangement::Aside(arrangement)))
+}
+
+/// Resolve the values of a string column, given inline or as a data source reference.
+/// Returns `None` if the column does not hold strings or is not found.
+fn resolve_str_col(
+    col: &Bound<'_, PyAny>,
+    data_src: &dyn data::Source,
+) -> Option<Vec<Option<String>>> {
+    if let Ok(src_ref) = col.extract::<String>() {
+        let col = data_src.column(&src_ref)?;
+        let col = col.str()?;
+        Some(col.str_iter().map(|v| v.map(str::to_string)).collect())
+    } else {
+        let values: Vec<String> = col.extract().ok()?;
+        Some(values.into_iter().map(Some).collect())
+    }
+}
+
+/// Combine the HBar series of a plot into a single group of stacked bars.
+/// In percent mode, the lengths are scaled so that each category sums to 100.
+fn extract_stacked_hbars(
+    py_series: &Bound<'_, PyList>,
+    percent: bool,
+    data_src: &dyn data::Source,
+) -> PyResult<des::series::BarsGroup> {
+    let mut categories = None;
+    let mut height = None;
+    let mut lengths = Vec::with_capacity(py_series.len());
+    let mut bar_series = Vec::with_capacity(py_series.len());
+
+    for (idx, ser) in py_series.iter().enumerate() {
+        let path = || format!("series[{}]", idx);
+        let cls_name = extract_class_name(&ser)?;
+        if cls_name != "HBar" {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Only HBar series can be stacked, but the plot stacks a {} series. Use stack_mode='none' to mix series types.",
+                cls_name
+            )))
+            .with_path(path);
+        }
+        check_inline_lengths(&ser, &cls_name).with_path(path)?;
+        check_src_refs(&ser, &cls_name, data_src).with_path(path)?;
+        let x = ser.getattr("x")?;
+        let y = ser.getattr("y")?;
+        check_hbar_cols(&x, &y, data_src).with_path(path)?;
+
+        let cats = resolve_str_col(&y, data_src);
+        match &categories {
+            None => categories = Some((extract_data_col(&y)?, cats)),
+            Some((_, first)) if *first != cats => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Stacked HBar series must all have the same 'y' categories, in the same order.",
+                ))
+                .with_path(path);
             }
+            Some(_) => {}
+        }
+        let ser_height = extract_hbar_height(&ser).with_path(path)?;
+        if idx > 0 && ser_height != height {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Stacked HBar series must all have the same height.",
+            ))
+            .with_path(path);
+        }
+        height = ser_height;
 
-            des::Series::Line(line)
+        let bars = extract_hbar_bar_series(&ser).with_path(path)?;
+        if percent {
+            let Some(values) = resolve_f64_col(&x, data_src) else {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Percent stacking requires numeric 'x' lengths.",
+                ))
+                .with_path(path);
+            };
+            if values.iter().flatten().any(|v| *v < 0.0) {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Percent stacking requires non-negative 'x' lengths.",
+                ))
+                .with_path(path);
+            }
+            lengths.push(values);
         }
-        _ => {
-            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
-                "Unsupported series type: {}",
-                cls_name
-            )));
+        bar_series.push(bars);
+    }
+
+    if percent {
+        // missing values count as zero in the total of their category
+        let num_cats = lengths.iter().map(Vec::len).max().unwrap_or(0);
+        let totals: Vec<f64> = (0..num_cats)
+            .map(|i| {
+                lengths
+                    .iter()
+                    .filter_map(|l| l.get(i).copied().flatten())
+                    .filter(|v| v.is_finite())
+                    .sum()
+            })
+            .collect();
+        for (bars, values) in bar_series.iter_mut().zip(lengths) {
+            let scaled: Vec<f64> = values
+                .iter()
+                .zip(&totals)
+                .map(|(v, total)| match v {
+                    Some(v) if *total > 0.0 => v / total * 100.0,
+                    Some(_) => 0.0,
+                    None => f64::NAN,
+                })
+                .collect();
+            let mut scaled_bars = des::series::BarSeries::new(des::DataCol::Inline(scaled.into()))
+                .with_fill(*bars.fill());
+            if let Some(name) = bars.name() {
+                scaled_bars = scaled_bars.with_name(name);
+            }
+            *bars = scaled_bars;
         }
+    }
+
+    let Some((categories, _)) = categories else {
+        unreachable!("stacked plots have at least one series");
     };
+    let mut arrangement = des::series::BarsStackArrangement::default();
+    if let Some(height) = height {
+        // centered in the category bin
+        arrangement.offset = (1.0 - height) / 2.0;
+        arrangement.width = height;
+    }
+    Ok(des::series::BarsGroup::new(categories, bar_series)
+        .with_orientation(des::series::BarsOrientation::Horizontal)
+        .with_arrangement(des::series::BarsArrangement::Stack(arrangement)))
+}
 
-    Ok(series)
+fn extract_histogram_bins(py_bins: &Bound<'_, PyAny>) -> PyResult<u32> {
+    let Ok(count) = py_bins.extract::<u32>() else {
+        return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Histogram bins must be a positive integer (bin count). Got {}.",
+            describe_value(py_bins)
+        )));
+    };
+    if count == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "Histogram bins count must be at least 1.",
+        ));
+    }
+    Ok(count)
 }
 
+fn extract_histogram_series(
+    ser: &Bound<'_, PyAny>,
+    data_src: &dyn data::Source,
+) -> PyResult<des::series::Histogram> {
+    let values = ser.getattr("values")?;
+    if let Some(resolved) = resolve_f64_col(&values, data_src)
+        && !resolved.iter().flatten().any(|v| v.is_finite())
+    {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "Histogram values must contain at least one finite value.",
+        ));
+    }
+    let data = extract_data_col(&values)?;
+
+    let mut hist = des::series::Histogram::new(data);
+    if let Some(name) = getattr_not_none(ser, "name")?
+        && is_series_in_legend(ser)?
+    {
+        let name_str: String = name.extract()?;
+        hist = hist.with_name(name_str);
+    }
+    if let Some(py_x_axis) = getattr_not_none(ser, "x_axis")? {
+        let x_axis = extract_axis_ref(&py_x_axis)?;
+        hist = hist.with_x_axis(x_axis);
+    }
+    if let Some(py_y_axis) = getattr_not_none(ser, "y_axis")? {
+        let y_axis = extract_axis_ref(&py_y_axis)?;
+        hist = hist.with_y_axis(y_axis);
+    }
+    if let Some(py_bins) = getattr_not_none(ser, "bins")? {
+        let bins = extract_histogram_bins(&py_bins)?;
+        hist = hist.with_bins(bins);
+    }
+    if let Some(py_density) = getattr_not_none(ser, "density")?
+        && py_density.extract::<bool>()?
+    {
+        hist = hist.with_density();
+    }
+    if let Some(py_color) = getattr_not_none(ser, "color")? {
+        let color = extract_series_color(&py_color)?;
+        hist = hist.with_fill(style::series::Fill::Solid {
+            color,
+            opacity: None,
+        });
+    }
+    Ok(hist)
+}
 fn extract_axis_range(py_range: &Bound<'_, PyAny>) -> PyResult<des::axis::Range> {
-    let (min, max): (Option<f64>, Option<f64>) = py_range.extract()?;
+    let (py_min, py_max): (Bound<'_, PyAny>, Bound<'_, PyAny>) = py_range.extract()?;
+    let extract_bound = |b: &Bound<'_, PyAny>| -> PyResult<Option<f64>> {
+        if b.is_none() {
+            Ok(None)
+        } else {
+            extract_f64_like(b).map(Some)
+        }
+    };
+    let min = extract_bound(&py_min)?;
+    let max = extract_bound(&py_max)?;
     Ok(des::axis::Range(min, max))
 }
 
@@ -219,7 +802,7 @@ fn extract_ticks_formatter(
                 .map(|f| f.extract())
                 .transpose()?;
             let formatter = fmt
-                .map(|f| des::axis::ticks::DateTimeFormatter::Custom(f))
+                .map(des::axis::ticks::DateTimeFormatter::Custom)
                 .unwrap_or_else(|| des::axis::ticks::DateTimeFormatter::Auto);
             Ok(formatter.into())
         }
@@ -229,7 +812,7 @@ fn extract_ticks_formatter(
                 .map(|f| f.extract())
                 .transpose()?;
             let formatter = fmt
-                .map(|f| des::axis::ticks::TimeDeltaFormatter::Custom(f))
+                .map(des::axis::ticks::TimeDeltaFormatter::Custom)
                 .unwrap_or_else(|| des::axis::ticks::TimeDeltaFormatter::Auto);
             Ok(formatter.into())
         }
@@ -255,7 +838,9 @@ fn extract_axis_ticks(py_ticks: &Bound<'_, PyAny>) -> PyResult<des::axis::Ticks>
     Ok(ticks)
 }
 
-fn extract_axis(py_axis: &Bound<'_, PyAny>) -> PyResult<des::Axis> {
+/// `time_values` is set when a series draws datetime values along the axis.
+/// Datetime ticks are then used unless ticks are specified.
+fn extract_axis(py_axis: &Bound<'_, PyAny>, time_values: bool) -> PyResult<des::Axis> {
     let mut axis = des::Axis::new().with_scale(extract_axis_scale(&py_axis.getattr("scale")?)?);
 
     if let Some(py_title) = getattr_not_none(py_axis, "title")? {
@@ -275,13 +860,43 @@ fn extract_axis(py_axis: &Bound<'_, PyAny>) -> PyResult<des::Axis> {
         }
     }
 
-    if let Some(py_ticks) = getattr_not_none(py_axis, "ticks")? {
-        let ticks = extract_axis_ticks(&py_ticks)?;
+    let py_ticks = getattr_not_none(py_axis, "ticks")?;
+    let py_tick_fontsize = getattr_not_none(py_axis, "tick_fontsize")?;
+    if py_ticks.is_some() || py_tick_fontsize.is_some() || time_values {
+        let mut ticks = if let Some(py_ticks) = py_ticks {
+            extract_axis_ticks(&py_ticks)?
+        } else if time_values {
+            des::axis::Ticks::default()
+                .with_locator(des::axis::ticks::DateTimeLocator::Auto.into())
+                .with_formatter(Some(des::axis::ticks::DateTimeFormatter::Auto.into()))
+        } else {
+            des::axis::Ticks::default()
+        };
+        if let Some(py_fontsize) = py_tick_fontsize {
+            let fontsize = extract_f32_like(&py_fontsize)?;
+            if fontsize <= 0.0 {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Tick font size must be positive, got {}",
+                    fontsize
+                )));
+            }
+            let font = des::axis::ticks::TicksFont {
+                size: fontsize,
+                ..ticks.font().clone()
+            };
+            ticks = ticks.with_font(font);
+        }
         axis = axis.with_ticks(ticks);
     }
 
-    if let Some(py_grid) = getattr_not_none(py_axis, "grid")? {
+    // width of the major grid, used to derive the minor grid width when unspecified
+    let mut grid_width = 1.0;
+    // "none" leaves the axis without grid, which is the default of plotive
+    if let Some(py_grid) = getattr_not_none(py_axis, "grid")?
+        && !is_none_str(&py_grid)
+    {
         let stroke = extract_theme_stroke(&py_grid)?;
+        grid_width = stroke.width;
         axis = axis.with_grid(stroke.into());
     }
 
@@ -291,14 +906,123 @@ fn extract_axis(py_axis: &Bound<'_, PyAny>) -> PyResult<des::Axis> {
         axis = axis.with_minor_ticks(minor_ticks);
     }
 
-    if let Some(py_minor_grid) = getattr_not_none(py_axis, "minor_grid")? {
-        let stroke = extract_theme_stroke(&py_minor_grid)?;
+    if let Some(py_minor_grid) = getattr_not_none(py_axis, "minor_grid")?
+        && !is_none_str(&py_minor_grid)
+    {
+        let mut stroke = extract_theme_stroke(&py_minor_grid)?;
+        if getattr_not_none(&py_minor_grid, "width")?.is_none() {
+            stroke.width = grid_width * MINOR_GRID_WIDTH_FACTOR;
+        }
         axis = axis.with_minor_grid(stroke.into());
     }
 
     Ok(axis)
 }
 
+/// Width of the minor grid relative to the major grid, when not specified.
+const MINOR_GRID_WIDTH_FACTOR: f32 = 0.5;
+
+/// Check whether `obj` is the `"none"` string, used to explicitly disable an element.
+fn is_none_str(obj: &Bound<'_, PyAny>) -> bool {
+    obj.extract::<&str>()
+        .is_ok_and(|s| s.eq_ignore_ascii_case("none"))
+}
+
+/// For each axis of a plot dimension, whether a series has a datetime column
+/// of the data source as values of that dimension.
+/// `dim` is `"x"` or `"y"`, the series attribute of the values.
+fn plot_time_axes(
+    py_series: &Bound<'_, PyList>,
+    py_axes: &Bound<'_, PyList>,
+    dim: &str,
+    data_src: &dyn data::Source,
+) -> PyResult<Vec<bool>> {
+    let mut time_axes = vec![false; py_axes.len()];
+    for ser in py_series.iter() {
+        let Some(py_col) = ser.getattr_opt(dim)? else {
+            continue;
+        };
+        let Ok(col) = py_col.extract::<String>() else {
+            continue;
+        };
+        if data_src.column(&col).and_then(|col| col.time()).is_none() {
+            continue;
+        }
+        let idx = match getattr_not_none(&ser, &format!("{}_axis", dim))? {
+            None => Some(0),
+            Some(py_ref) => match extract_axis_ref(&py_ref)? {
+                des::axis::Ref::Idx(idx) => Some(idx),
+                des::axis::Ref::Id(id) => find_axis_by_id_or_title(py_axes, &id)?,
+                // only used by shared scales, never by series
+                des::axis::Ref::FigIdx(_) => None,
+            },
+        };
+        if let Some(time_axis) = idx.and_then(|idx| time_axes.get_mut(idx)) {
+            *time_axis = true;
+        }
+    }
+    Ok(time_axes)
+}
+
+fn find_axis_by_id_or_title(py_axes: &Bound<'_, PyList>, id: &str) -> PyResult<Option<usize>> {
+    for (idx, py_axis) in py_axes.iter().enumerate() {
+        for attr in ["id", "title"] {
+            if let Some(py_value) = getattr_not_none(&py_axis, attr)?
+                && py_value.extract::<String>().is_ok_and(|v| v == id)
+            {
+                return Ok(Some(idx));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Extract the axes of one plot dimension.
+/// A twin axis without explicit side is put on the side opposite to the axis it references.
+/// Its scale remains independent, unless a `SharedScale` is given.
+/// `name` is the plot attribute of the axes, used to locate errors.
+fn extract_plot_axes(
+    py_axes: &Bound<'_, PyList>,
+    name: &str,
+    time_axes: &[bool],
+) -> PyResult<Vec<des::Axis>> {
+    let mut axes = Vec::with_capacity(py_axes.len());
+    for (idx, py_axis) in py_axes.iter().enumerate() {
+        let time_values = time_axes.get(idx).copied().unwrap_or(false);
+        let mut axis =
+            extract_axis(&py_axis, time_values).with_path(|| format!("{}[{}]", name, idx))?;
+        if let Some(py_twin) = getattr_not_none(&py_axis, "twin")?
+            && getattr_not_none(&py_axis, "opposite_side")?.is_none()
+        {
+            let twin: String = py_twin.extract()?;
+            let mut twin_opposite_side = None;
+            for py_other in py_axes.iter() {
+                if let Some(py_id) = getattr_not_none(&py_other, "id")?
+                    && py_id.extract::<String>()? == twin
+                {
+                    let opposite_side = match getattr_not_none(&py_other, "opposite_side")? {
+                        Some(os) => os.extract::<bool>()?,
+                        None => false,
+                    };
+                    twin_opposite_side = Some(opposite_side);
+                    break;
+                }
+            }
+            let Some(twin_opposite_side) = twin_opposite_side else {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Twin axis reference '{}' does not match any axis id of the same dimension.",
+                    twin
+                )));
+            };
+            if !twin_opposite_side {
+                axis = axis.with_opposite_side();
+            }
+        }
+        axes.push(axis);
+    }
+    Ok(axes)
+}
+
 fn extract_legend<P: Default>(py_legend: &Bound<'_, PyAny>, pos: P) -> PyResult<des::Legend<P>> {
     let mut legend = des::Legend::new(pos);
     if let Some(py_columns) = getattr_not_none(py_legend, "columns")? {
@@ -317,18 +1041,19 @@ fn extract_legend<P: Default>(py_legend: &Bound<'_, PyAny>, pos: P) -> PyResult<
         }
     }
     if let Some(py_spacing) = getattr_not_none(py_legend, "spacing")? {
-        if let Ok(spacing) = py_spacing.extract::<f32>() {
+        if let Ok(spacing) = extract_f32_like(&py_spacing) {
             legend = legend.with_spacing(geom::Size::new(spacing, spacing));
         } else if let Ok((h, v)) = py_spacing.extract::<(f32, f32)>() {
             legend = legend.with_spacing(geom::Size::new(h, v));
         } else {
-            return Err(pyo3::exceptions::PyTypeError::new_err(
-                "Legend spacing must be a float or a tuple of two floats.",
-            ));
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Legend spacing must be a float or a tuple of two floats. Got {}.",
+                describe_value(&py_spacing)
+            )));
         }
     }
     if let Some(py_margin) = getattr_not_none(py_legend, "margin")? {
-        let margin = py_margin.extract::<f32>()?;
+        let margin = extract_f32_like(&py_margin)?;
         legend = legend.with_margin(margin);
     }
     Ok(legend)
@@ -359,7 +1084,7 @@ fn extract_plot_legend(py_legend: &Bound<'_, PyAny>) -> PyResult<des::PlotLegend
             }
         }
     }
-    Ok(extract_legend(py_legend, pos)?)
+    extract_legend(py_legend, pos)
 }
 
 fn extract_figure_legend(py_legend: &Bound<'_, PyAny>) -> PyResult<des::FigLegend> {
@@ -379,77 +1104,251 @@ fn extract_figure_legend(py_legend: &Bound<'_, PyAny>) -> PyResult<des::FigLegen
             }
         };
     }
-    Ok(extract_legend(py_legend, pos)?)
+    extract_legend(py_legend, pos)
 }
 
-fn extract_plot(py_plot: &Bound<'_, PyAny>) -> PyResult<des::Plot> {
-    let py_series = py_plot.getattr("series")?;
-    let py_series = py_series.cast::<PyList>()?;
+/// Extract the series of a plot, each drawn on its own, in zorder.
+fn extract_plot_series(
+    py_series: &Bound<'_, PyList>,
+    data_src: &dyn data::Source,
+) -> PyResult<Vec<des::Series>> {
     let mut series = Vec::with_capacity(py_series.len());
 
-    for ser in py_series.iter() {
-        let ser = extract_series(&ser)?;
-        series.push(ser);
+    for (idx, ser) in py_series.iter().enumerate() {
+        let zorder = if let Some(py_zorder) = getattr_not_none(&ser, "zorder")? {
+            py_zorder.extract::<i32>()?
+        } else {
+            0
+        };
+        let ser = extract_series(&ser, data_src).with_path(|| format!("series[{}]", idx))?;
+        series.push((zorder, ser));
     }
+    // series are drawn in order, so the highest zorder must come last.
+    // sort_by_key is stable, equal zorder keep insertion order.
+    series.sort_by_key(|(zorder, _)| *zorder);
+    Ok(series.into_iter().map(|(_, ser)| ser).collect())
+}
+
+fn extract_plot(py_plot: &Bound<'_, PyAny>, data_src: &dyn data::Source) -> PyResult<des::Plot> {
+    let py_series = py_plot.getattr("series")?;
+    let py_series = py_series.cast::<PyList>()?;
+    let py_annots = py_plot.getattr("annotations")?;
+    let py_annots = py_annots.cast::<PyList>()?;
+    // annotation-only plots are allowed, but a plot with nothing to draw is most likely a mistake
+    if py_series.is_empty() && py_annots.is_empty() {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "Plot must contain at least one series or annotation.",
+        ));
+    }
+    let stack_mode: String = match getattr_not_none(py_plot, "stack_mode")? {
+        Some(py_stack_mode) => py_stack_mode.extract()?,
+        None => "none".into(),
+    };
+    let series = match stack_mode.as_str() {
+        "none" => extract_plot_series(py_series, data_src)?,
+        "stacked" | "percent" if !py_series.is_empty() => {
+            let group = extract_stacked_hbars(py_series, stack_mode == "percent", data_src)?;
+            vec![des::Series::BarsGroup(group)]
+        }
+        "stacked" | "percent" => Vec::new(),
+        _ => {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown stack mode: {} (expected 'none', 'stacked' or 'percent')",
+                stack_mode
+            )));
+        }
+    };
     let mut plot = des::Plot::new(series);
 
     if let Some(py_legend) = getattr_not_none(py_plot, "legend")? {
-        let legend = extract_plot_legend(&py_legend)?;
+        let legend = extract_plot_legend(&py_legend).with_path(|| "legend".into())?;
         plot = plot.with_legend(legend);
     }
 
     let py_title = py_plot.getattr("title")?;
     if !py_title.is_none() {
-        let title: String = py_title.extract()?;
-        plot = plot.with_title(title.into());
+        let title: String = py_title.extract().with_path(|| "title".into())?;
+        plot = plot.with_title(title);
     }
 
     let py_x_axes = py_plot.getattr("x_axes")?;
     let py_x_axes = py_x_axes.cast::<PyList>()?;
-    for py_x_axis in py_x_axes.iter() {
-        let x_axis = extract_axis(&py_x_axis)?;
+    let time_x_axes = plot_time_axes(py_series, py_x_axes, "x", data_src)?;
+    for x_axis in extract_plot_axes(py_x_axes, "x_axes", &time_x_axes)? {
         plot = plot.with_x_axis(x_axis);
     }
 
     let py_y_axes = py_plot.getattr("y_axes")?;
     let py_y_axes = py_y_axes.cast::<PyList>()?;
-    for py_y_axis in py_y_axes.iter() {
-        let y_axis = extract_axis(&py_y_axis)?;
+    let time_y_axes = plot_time_axes(py_series, py_y_axes, "y", data_src)?;
+    for y_axis in extract_plot_axes(py_y_axes, "y_axes", &time_y_axes)? {
         plot = plot.with_y_axis(y_axis);
     }
 
-    let py_annots = py_plot.getattr("annotations")?;
-    let py_annots = py_annots.cast::<PyList>()?;
-    for py_annot in py_annots.iter() {
-        let annot = extract_annot(&py_annot)?;
+    for (idx, py_annot) in py_annots.iter().enumerate() {
+        let annot = extract_annot(&py_annot).with_path(|| format!("annotations[{}]", idx))?;
         plot = plot.with_annotation(annot);
     }
 
+    if let Some(py_spines) = getattr_not_none(py_plot, "spines")? {
+        let border = extract_plot_border(&py_spines, plot.x_axes(), plot.y_axes())
+            .with_path(|| "spines".into())?;
+        plot = plot.with_border(border);
+    }
+
+    if let Some(py_margin) = getattr_not_none(py_plot, "margin")? {
+        let insets = extract_plot_insets(&py_margin).with_path(|| "margin".into())?;
+        plot = plot.with_insets(Some(insets));
+    }
+
     Ok(plot)
 }
 
+/// Extract the space between the data and the plot frame, in figure units.
+/// Either a single value, or a `(horizontal, vertical)` tuple.
+fn extract_plot_insets(py_margin: &Bound<'_, PyAny>) -> PyResult<des::plot::Insets> {
+    let (x, y) = if let Ok(margin) = extract_f32_like(py_margin) {
+        (margin, margin)
+    } else if let Ok((x, y)) = py_margin.extract::<(f32, f32)>() {
+        (x, y)
+    } else {
+        return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Plot margin must be a float or a tuple of two floats. Got {}.",
+            describe_value(py_margin)
+        )));
+    };
+    if !(x >= 0.0 && x.is_finite() && y >= 0.0 && y.is_finite()) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Plot margin must be non-negative, got ({}, {})",
+            x, y
+        )));
+    }
+    Ok(des::plot::Insets::Fixed(x, y))
+}
+
+/// Apply the `title_fontsize` and `title_color` attributes of the figure.
+/// `des::TitleProps` has no font size setter, so the size is set on a span covering the
+/// whole title, ahead of the rich text spans so that these can still override it.
+fn extract_title_style(
+    py_fig: &Bound<'_, PyAny>,
+    mut title: des::figure::Title,
+) -> PyResult<des::figure::Title> {
+    if let Some(py_fontsize) = getattr_not_none(py_fig, "title_fontsize")? {
+        let fontsize = extract_f32_like(&py_fontsize)?;
+        if !(fontsize > 0.0 && fontsize.is_finite()) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Title font size must be positive, got {}",
+                fontsize
+            )));
+        }
+        let size_span = des::figure::TitleOptProps {
+            font_size: Some(fontsize),
+            ..Default::default()
+        };
+        let mut spans = vec![(0, title.text().len(), size_span)];
+        spans.extend_from_slice(title.spans());
+        title = title.with_spans(spans);
+    }
+    if let Some(py_color) = getattr_not_none(py_fig, "title_color")? {
+        let color = extract_theme_color(&py_color)?;
+        let props = title.props().clone().with_fill(Some(color));
+        title = title.with_props(props);
+    }
+    Ok(title)
+}
+
+const SPINES: [&str; 4] = ["left", "right", "top", "bottom"];
+
+/// Map the list of spines to keep visible to a plot border.
+/// plotive draws either a box, lines on the sides holding an axis, or no border at all,
+/// so the spines must be all the sides, the sides of the axes, or none.
+fn extract_plot_border(
+    py_spines: &Bound<'_, PyAny>,
+    x_axes: &[des::Axis],
+    y_axes: &[des::Axis],
+) -> PyResult<Option<des::plot::Border>> {
+    let names: Vec<String> = py_spines.extract()?;
+    let mut spines = [false; 4];
+    for name in names {
+        let Some(idx) = SPINES.iter().position(|s| *s == name) else {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown spine: {} (expected 'left', 'right', 'top' or 'bottom')",
+                name
+            )));
+        };
+        spines[idx] = true;
+    }
+
+    let mut axis_spines = [false; 4];
+    for axis in y_axes {
+        match axis.side() {
+            des::axis::Side::Main => axis_spines[0] = true,
+            des::axis::Side::Opposite => axis_spines[1] = true,
+        }
+    }
+    for axis in x_axes {
+        match axis.side() {
+            des::axis::Side::Main => axis_spines[3] = true,
+            des::axis::Side::Opposite => axis_spines[2] = true,
+        }
+    }
+
+    let stroke: style::theme::Stroke = style::theme::Col::Foreground.into();
+    if spines == [true; 4] {
+        Ok(Some(des::plot::Border::Box(stroke)))
+    } else if spines == [false; 4] {
+        Ok(None)
+    } else if spines == axis_spines {
+        Ok(Some(des::plot::Border::Axis(stroke)))
+    } else {
+        let axis_sides: Vec<&str> = SPINES
+            .iter()
+            .zip(axis_spines)
+            .filter_map(|(s, visible)| visible.then_some(*s))
+            .collect();
+        Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Spines must be all the sides, none, or the sides of the axes ({})",
+            axis_sides.join(", ")
+        )))
+    }
+}
+
 fn extract_row_col(subplot: &Bound<'_, PyAny>) -> PyResult<(u32, u32)> {
     if let Ok(tuple) = subplot.extract::<(u32, u32)>() {
         return Ok(tuple);
-    } else if let Ok(list) = subplot.cast::<PyList>() {
-        if list.len() == 2 {
-            return Ok((list.get_item(0)?.extract()?, list.get_item(1)?.extract()?));
-        }
+    } else if let Ok(list) = subplot.cast::<PyList>()
+        && list.len() == 2
+    {
+        return Ok((list.get_item(0)?.extract()?, list.get_item(1)?.extract()?));
     }
-    Err(pyo3::exceptions::PyTypeError::new_err(
-        "Subplot must be a tuple or list of two integers (rows, cols).",
-    ))
+    Err(pyo3::exceptions::PyTypeError::new_err(format!(
+        "Subplot must be a tuple or list of two integers (rows, cols). Got {}.",
+        describe_value(subplot)
+    )))
+}
+
+/// Extract the cell of a subplot as `(row, col)`, starting at 1.
+fn extract_subplot_cell(subplot: &Bound<'_, PyAny>) -> PyResult<(u32, u32)> {
+    let cell = extract_row_col(subplot)?;
+    if cell.0 == 0 || cell.1 == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid subplot {:?}: rows and columns start at 1.",
+            cell
+        )));
+    }
+    Ok(cell)
 }
 
 fn extract_plots(
     py_plots: &Bound<'_, PyAny>,
     subplots: Option<(u32, u32)>,
     space: Option<f32>,
+    data_src: &dyn data::Source,
 ) -> PyResult<des::figure::Plots> {
     let py_plots = py_plots.cast::<PyList>()?;
     if py_plots.len() == 1 {
         let py_plot = py_plots.get_item(0)?;
-        let plot = extract_plot(&py_plot)?;
+        let plot = extract_plot(&py_plot, data_src).with_path(|| "figure.plots[0]".into())?;
         return Ok(plot.into());
     }
 
@@ -462,11 +1361,13 @@ fn extract_plots(
     let mut plots = Vec::with_capacity(py_plots.len());
     let mut max_sp: Option<(u32, u32)> = None;
 
-    for py_plot in py_plots.iter() {
-        let plot = extract_plot(&py_plot)?;
+    for (idx, py_plot) in py_plots.iter().enumerate() {
+        let plot_path = || format!("figure.plots[{}]", idx);
+        let plot = extract_plot(&py_plot, data_src).with_path(plot_path)?;
         let subplot = getattr_not_none(&py_plot, "subplot")?
-            .map(|sp| extract_row_col(&sp))
-            .transpose()?;
+            .map(|sp| extract_subplot_cell(&sp))
+            .transpose()
+            .with_path(|| format!("{}.subplot", plot_path()))?;
         match (subplot, &mut max_sp) {
             (None, None) => (),
             (Some(sp), Some(subplots)) => {
@@ -500,11 +1401,21 @@ fn extract_plots(
     // but des has rows and cols starting at 0
     let mut row = 0;
     let mut col = 0;
-    for (sp, plot) in plots {
+    let mut occupied = HashMap::new();
+    for (idx, (sp, plot)) in plots.into_iter().enumerate() {
         let (r, c) = match sp {
             Some((r, c)) => (r - 1, c - 1),
             None => (row, col),
         };
+        if let Some(other) = occupied.insert((r, c), idx) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Plots {} and {} are both placed at subplot ({}, {}).",
+                other,
+                idx,
+                r + 1,
+                c + 1
+            )));
+        }
         subplots = subplots.with_plot((r, c), plot);
         row += 1;
         if row >= rows {
@@ -520,9 +1431,64 @@ fn extract_plots(
     Ok(subplots.into())
 }
 
-pub fn extract_figure(py_fig: &Bound<'_, PyAny>) -> PyResult<des::Figure> {
+fn extract_figure_fill(py_fig: &Bound<'_, PyAny>) -> PyResult<Option<style::theme::Fill>> {
+    let py_fill = py_fig.getattr_opt("fill")?;
+    let mut fill = py_fill
+        .map(|f| extract_theme_color(&f))
+        .transpose()?
+        .map(|color| style::theme::Fill::Solid {
+            color,
+            opacity: None,
+        });
+    if let Some(py_opacity) = getattr_not_none(py_fig, "fill_opacity")? {
+        let fill_opacity = extract_opacity(&py_opacity)?;
+        let Some(f) = fill else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "fill_opacity requires a figure fill.",
+            ));
+        };
+        fill = Some(f.with_opacity(fill_opacity));
+    }
+    Ok(fill)
+}
+
+fn extract_figure_size(py_fig: &Bound<'_, PyAny>) -> PyResult<Option<geom::Size>> {
+    if let Some(py_size) = getattr_not_none(py_fig, "size")? {
+        let (w, h): (f32, f32) = py_size.extract()?;
+        Ok(Some(geom::Size::new(w, h)))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Extract each plot of the figure as a standalone single-plot figure.
+/// The figures have the size and fill of the original figure, but no title or legend.
+pub fn extract_plot_figures(
+    py_fig: &Bound<'_, PyAny>,
+    data_src: &dyn data::Source,
+) -> PyResult<Vec<des::Figure>> {
+    let fill = extract_figure_fill(py_fig)?;
+    let py_plots = py_fig.getattr("plots")?;
+    let py_plots = py_plots.cast::<PyList>()?;
+    let mut figs = Vec::with_capacity(py_plots.len());
+    for (idx, py_plot) in py_plots.iter().enumerate() {
+        let plot =
+            extract_plot(&py_plot, data_src).with_path(|| format!("figure.plots[{}]", idx))?;
+        let mut fig = des::Figure::new(plot.into()).with_fill(fill);
+        if let Some(size) = extract_figure_size(py_fig)? {
+            fig = fig.with_size(size);
+        }
+        figs.push(fig);
+    }
+    Ok(figs)
+}
+
+pub fn extract_figure(
+    py_fig: &Bound<'_, PyAny>,
+    data_src: &dyn data::Source,
+) -> PyResult<des::Figure> {
     let space = if let Ok(space) = py_fig.getattr("space") {
-        Some(space.extract::<f32>()?)
+        Some(extract_f32_like(&space)?)
     } else {
         None
     };
@@ -532,32 +1498,27 @@ pub fn extract_figure(py_fig: &Bound<'_, PyAny>) -> PyResult<des::Figure> {
         None
     };
     let py_plots = py_fig.getattr("plots")?;
-    let plots = extract_plots(&py_plots, subplots, space)?;
-
-    let py_fill = py_fig.getattr_opt("fill")?;
-    let fill = py_fill
-        .map(|f| extract_theme_color(&f))
-        .transpose()?
-        .and_then(|c| {
-            style::theme::Fill::Solid {
-                color: c,
-                opacity: None,
-            }
-            .into()
-        });
+    let plots = extract_plots(&py_plots, subplots, space, data_src)?;
 
+    let fill = extract_figure_fill(py_fig).with_path(|| "figure.fill".into())?;
     let mut fig = des::Figure::new(plots).with_fill(fill);
 
+    if let Some(size) = extract_figure_size(py_fig)? {
+        fig = fig.with_size(size);
+    }
+
     if let Some(py_title) = getattr_not_none(py_fig, "title")? {
         let title_fmt: String = py_title.extract()?;
         let title = plotive_text::parse_rich_text(&title_fmt).map_err(|e| {
             pyo3::exceptions::PyValueError::new_err(format!("Failed to parse plot title: {}", e))
         })?;
-        fig = fig.with_title(title.into());
+        let title =
+            extract_title_style(py_fig, title.into()).with_path(|| "figure.title".into())?;
+        fig = fig.with_title(title);
     }
 
     if let Some(py_legend) = getattr_not_none(py_fig, "legend")? {
-        let legend = extract_figure_legend(&py_legend)?;
+        let legend = extract_figure_legend(&py_legend).with_path(|| "figure.legend".into())?;
         fig = fig.with_legend(legend);
     }
     Ok(fig)
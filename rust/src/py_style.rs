@@ -2,27 +2,97 @@ use plotive::ColorU8;
 use plotive::style;
 
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyDict, PyList, PyTuple};
 
-use super::{getattr_not_none, extract_color};
+use super::{getattr_not_none, extract_class_name, extract_color, extract_f32_like};
+
+/// Table of predefined names, with the constructor of the named item.
+type NamedTable<T> = &'static [(&'static str, fn() -> T)];
+
+/// Names of the predefined styles, with their aliases.
+/// This is the single source for both `extract_style` and `available_styles`.
+pub const STYLES: NamedTable<plotive::Style> = &[
+    ("black_white", plotive::Style::black_white),
+    ("monochrome", plotive::Style::black_white),
+    ("black", plotive::Style::black_white),
+    ("light", plotive::Style::light),
+    ("dark", plotive::Style::dark),
+    ("okabe_ito", plotive::Style::okabe_ito),
+    ("okabe", plotive::Style::okabe_ito),
+    ("tol_bright", plotive::Style::tol_bright),
+    ("tol", plotive::Style::tol_bright),
+    ("mocha", plotive::Style::catppuccin_mocha),
+    ("catppuccin-mocha", plotive::Style::catppuccin_mocha),
+    ("macchiato", plotive::Style::catppuccin_macchiato),
+    ("catppuccin-macchiato", plotive::Style::catppuccin_macchiato),
+    ("frappe", plotive::Style::catppuccin_frappe),
+    ("catppuccin-frappe", plotive::Style::catppuccin_frappe),
+    ("latte", plotive::Style::catppuccin_latte),
+    ("catppuccin-latte", plotive::Style::catppuccin_latte),
+];
+
+/// Names of the predefined themes, with their aliases.
+pub const THEMES: NamedTable<style::theme::Theme> = &[
+    ("light", || style::theme::Theme::Light),
+    ("dark", || style::theme::Theme::Dark),
+    ("mocha", || style::theme::Theme::CatppuccinMocha),
+    ("catppuccin-mocha", || style::theme::Theme::CatppuccinMocha),
+    ("macchiato", || style::theme::Theme::CatppuccinMacchiato),
+    ("catppuccin-macchiato", || {
+        style::theme::Theme::CatppuccinMacchiato
+    }),
+    ("frappe", || style::theme::Theme::CatppuccinFrappe),
+    ("catppuccin-frappe", || {
+        style::theme::Theme::CatppuccinFrappe
+    }),
+    ("latte", || style::theme::Theme::CatppuccinLatte),
+    ("catppuccin-latte", || style::theme::Theme::CatppuccinLatte),
+];
+
+/// Names of the predefined series palettes, with their aliases.
+pub const PALETTES: NamedTable<style::series::Palette> = &[
+    ("black", || style::series::Palette::Black),
+    ("standard", || style::series::Palette::Standard),
+    ("default", || style::series::Palette::Standard),
+    ("pastel", || style::series::Palette::Pastel),
+    ("tol_bright", || style::series::Palette::TolBright),
+    ("tol", || style::series::Palette::TolBright),
+    ("okabe_ito", || style::series::Palette::OkabeIto),
+    ("okabe", || style::series::Palette::OkabeIto),
+    ("mocha", || style::series::Palette::CatppuccinMocha),
+    ("catppuccin-mocha", || {
+        style::series::Palette::CatppuccinMocha
+    }),
+    ("macchiato", || style::series::Palette::CatppuccinMacchiato),
+    ("catppuccin-macchiato", || {
+        style::series::Palette::CatppuccinMacchiato
+    }),
+    ("frappe", || style::series::Palette::CatppuccinFrappe),
+    ("catppuccin-frappe", || {
+        style::series::Palette::CatppuccinFrappe
+    }),
+    ("latte", || style::series::Palette::CatppuccinLatte),
+    ("catppuccin-latte", || {
+        style::series::Palette::CatppuccinLatte
+    }),
+];
+
+/// Look up `name` in a table of predefined names
+fn lookup_name<T>(table: NamedTable<T>, name: &str) -> Option<T> {
+    table
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, make)| make())
+}
 
 pub fn extract_style(py_style: &Bound<'_, PyAny>) -> PyResult<plotive::Style> {
     if let Ok(py_str) = py_style.extract::<&str>() {
-        return match py_str {
-            "black_white" | "monochrome" | "black" => Ok(plotive::Style::black_white()),
-            "light" => Ok(plotive::Style::light()),
-            "dark" => Ok(plotive::Style::dark()),
-            "okabe_ito" | "okabe" => Ok(plotive::Style::okabe_ito()),
-            "tol_bright" | "tol" => Ok(plotive::Style::tol_bright()),
-            "mocha" | "catppuccin-mocha" => Ok(plotive::Style::catppuccin_mocha()),
-            "macchiato" | "catppuccin-macchiato" => Ok(plotive::Style::catppuccin_macchiato()),
-            "frappe" | "catppuccin-frappe" => Ok(plotive::Style::catppuccin_frappe()),
-            "latte" | "catppuccin-latte" => Ok(plotive::Style::catppuccin_latte()),
-            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+        return lookup_name(STYLES, py_str).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
                 "Unknown name for plotive's style: {}",
                 py_str
-            ))),
-        };
+            ))
+        });
     }
     let Some(theme) = getattr_not_none(py_style, "theme")? else {
         return Err(pyo3::exceptions::PyValueError::new_err(
@@ -41,18 +111,9 @@ pub fn extract_style(py_style: &Bound<'_, PyAny>) -> PyResult<plotive::Style> {
 
 fn extract_theme(py_theme: &Bound<'_, PyAny>) -> PyResult<style::theme::Theme> {
     if let Ok(py_str) = py_theme.extract::<&str>() {
-        return match py_str {
-            "light" => Ok(style::theme::Theme::Light),
-            "dark" => Ok(style::theme::Theme::Dark),
-            "mocha" | "catppuccin-mocha" => Ok(style::theme::Theme::CatppuccinMocha),
-            "macchiato" | "catppuccin-macchiato" => Ok(style::theme::Theme::CatppuccinMacchiato),
-            "frappe" | "catppuccin-frappe" => Ok(style::theme::Theme::CatppuccinFrappe),
-            "latte" | "catppuccin-latte" => Ok(style::theme::Theme::CatppuccinLatte),
-            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
-                "Unknown theme name: {}",
-                py_str
-            ))),
-        };
+        return lookup_name(THEMES, py_str).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("Unknown theme name: {}", py_str))
+        });
     }
 
     let get_color_attr = |attr: &str| -> PyResult<ColorU8> {
@@ -83,21 +144,9 @@ fn extract_theme(py_theme: &Bound<'_, PyAny>) -> PyResult<style::theme::Theme> {
 
 fn extract_palette(py_palette: &Bound<'_, PyAny>) -> PyResult<style::series::Palette> {
     if let Ok(py_str) = py_palette.extract::<&str>() {
-        return match py_str {
-            "black" => Ok(style::series::Palette::Black),
-            "standard" | "default" => Ok(style::series::Palette::Standard),
-            "pastel" => Ok(style::series::Palette::Pastel),
-            "tol_bright" | "tol" => Ok(style::series::Palette::TolBright),
-            "okabe_ito" | "okabe" => Ok(style::series::Palette::OkabeIto),
-            "mocha" | "catppuccin-mocha" => Ok(style::series::Palette::CatppuccinMocha),
-            "macchiato" | "catppuccin-macchiato" => Ok(style::series::Palette::CatppuccinMacchiato),
-            "frappe" | "catppuccin-frappe" => Ok(style::series::Palette::CatppuccinFrappe),
-            "latte" | "catppuccin-latte" => Ok(style::series::Palette::CatppuccinLatte),
-            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
-                "Unknown palette name: {}",
-                py_str
-            ))),
-        };
+        return lookup_name(PALETTES, py_str).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("Unknown palette name: {}", py_str))
+        });
     }
     if let Ok(py_list) = py_palette.cast::<PyList>() {
         let mut colors = Vec::with_capacity(py_list.len());
@@ -107,12 +156,126 @@ fn extract_palette(py_palette: &Bound<'_, PyAny>) -> PyResult<style::series::Pal
         }
         return Ok(style::series::Palette::Custom(colors));
     }
+    if let Ok(py_dict) = py_palette.cast::<PyDict>() {
+        return extract_colormap_palette(py_dict);
+    }
     Err(pyo3::exceptions::PyTypeError::new_err(
-        "Palette must be a string or a list of colors.",
+        "Palette must be a string, a list of colors or a colormap sampling dict.",
     ))
 }
 
+/// Check that the palette of `style` has enough colors for the series of each plot,
+/// unless the figure allows the palette to cycle.
+/// This is enforced here, before rendering, as plotive always cycles through the palette.
+/// plotive picks the color of a series from its position in the plot, after sorting by zorder,
+/// so an automatic color cycles as soon as its series comes after the end of the palette.
+pub fn check_palette_cycle(py_fig: &Bound<'_, PyAny>, style: &plotive::Style) -> PyResult<()> {
+    if let Some(py_cycle) = getattr_not_none(py_fig, "palette_cycle")?
+        && py_cycle.extract::<bool>()?
+    {
+        return Ok(());
+    }
+    let palette_len = style.palette().len();
+    let py_plots = py_fig.getattr("plots")?;
+    for (idx, py_plot) in py_plots.cast::<PyList>()?.iter().enumerate() {
+        let py_series = py_plot.getattr("series")?;
+        let mut series = Vec::new();
+        for ser in py_series.cast::<PyList>()?.iter() {
+            let zorder = match getattr_not_none(&ser, "zorder")? {
+                Some(py_zorder) => py_zorder.extract::<i32>()?,
+                None => 0,
+            };
+            series.push((zorder, uses_palette_color(&ser)?));
+        }
+        // same stable sort as the plot extraction
+        series.sort_by_key(|(zorder, _)| *zorder);
+        if let Some(pos) = series
+            .iter()
+            .enumerate()
+            .position(|(pos, (_, auto))| *auto && pos >= palette_len)
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Plot {} has a series with automatic color at position {}, but the palette only has {} colors and palette_cycle is disabled",
+                idx + 1,
+                pos + 1,
+                palette_len
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Check whether the series color is picked from the palette.
+/// Only the series drawn with a single color have a palette color.
+fn uses_palette_color(ser: &Bound<'_, PyAny>) -> PyResult<bool> {
+    match extract_class_name(ser)?.as_str() {
+        "Line" | "Step" | "Scatter" | "HBar" | "Histogram" => {
+            let py_color = ser.getattr("color")?;
+            Ok(py_color.is_none() || py_color.extract::<&str>().is_ok_and(|c| c == "auto"))
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Build a palette of `n` colors evenly sampled from a colormap,
+/// from a dict such as `{"colormap": "viridis", "n": 6}`.
+fn extract_colormap_palette(py_dict: &Bound<'_, PyDict>) -> PyResult<style::series::Palette> {
+    let Some(py_cmap) = py_dict.get_item("colormap")? else {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "Palette dict must have a 'colormap' entry",
+        ));
+    };
+    let cmap = extract_colormap(&py_cmap)?;
+    let Some(py_n) = py_dict.get_item("n")? else {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "Palette dict must have an 'n' entry",
+        ));
+    };
+    let n: i64 = py_n.extract()?;
+    if n < 1 {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Palette colormap sampling requires n >= 1, got {}",
+            n
+        )));
+    }
+    let colors = (0..n)
+        .map(|i| {
+            let t = if n == 1 {
+                0.0
+            } else {
+                i as f32 / (n - 1) as f32
+            };
+            cmap.color_at(t)
+        })
+        .collect();
+    Ok(style::series::Palette::Custom(colors))
+}
+
 pub fn extract_stroke_pattern(pattern: &Bound<'_, PyAny>) -> PyResult<style::LinePattern> {
+    // (pattern, offset) form. A tuple of numbers is a plain dash pattern.
+    if let Ok(tup) = pattern.cast::<PyTuple>()
+        && tup.len() == 2
+        && extract_f32_like(&tup.get_item(0)?).is_err()
+    {
+        let dash = match extract_stroke_pattern(&tup.get_item(0)?)? {
+            style::LinePattern::Dash(dash) => dash,
+            // same pattern as plotive uses to draw dots
+            style::LinePattern::Dot => style::Dash(vec![1.0, 1.0]),
+            style::LinePattern::Solid => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Dash offset requires a dashed or dotted pattern",
+                ));
+            }
+        };
+        let offset = extract_f32_like(&tup.get_item(1)?)?;
+        if !offset.is_finite() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Dash offset must be finite, got {}",
+                offset
+            )));
+        }
+        return Ok(offset_dash(&dash.0, offset).into());
+    }
     if let Ok(s) = pattern.extract::<String>() {
         match s.as_str() {
             "solid" => return Ok(style::LinePattern::Solid),
@@ -127,14 +290,227 @@ pub fn extract_stroke_pattern(pattern: &Bound<'_, PyAny>) -> PyResult<style::Lin
         }
     }
     let pattern_vec: Vec<f32> = pattern.extract()?;
+    if pattern_vec.is_empty() || pattern_vec.iter().any(|&d| !(d > 0.0 && d.is_finite())) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Dash pattern must be a non-empty list of positive lengths, got {:?}",
+            pattern_vec
+        )));
+    }
     Ok(style::Dash(pattern_vec).into())
 }
 
-pub fn extract_series_color(py_col: &Bound<'_, PyAny>) -> PyResult<style::series::Color> {
-    if let Ok(col) = py_col.extract::<&str>() {
-        if col == "auto" {
-            return Ok(style::series::Color::Auto);
+/// Shift the dash pattern by `offset`, so that it starts `offset` into the pattern.
+/// `style::Dash` has no phase, so the pattern is rotated instead, splitting the
+/// segment at `offset` in two. Zero-length entries are merged with their neighbours,
+/// except a leading dash to start in a gap and a trailing gap to keep the dash/gap
+/// alternance. Strokes have butt caps, so these are not drawn.
+fn offset_dash(pattern: &[f32], offset: f32) -> style::Dash {
+    let mut pattern = pattern.to_vec();
+    // odd patterns are repeated to alternate dashes and gaps
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_within(..);
+    }
+    let total: f32 = pattern.iter().sum();
+    let mut offset = offset.rem_euclid(total);
+    let mut i = 0;
+    while i < pattern.len() - 1 && offset >= pattern[i] {
+        offset -= pattern[i];
+        i += 1;
+    }
+    let mut dash = Vec::with_capacity(pattern.len() + 2);
+    if i % 2 == 1 {
+        // starting in a gap
+        dash.push(0.0);
+    }
+    dash.push(pattern[i] - offset);
+    dash.extend_from_slice(&pattern[i + 1..]);
+    dash.extend_from_slice(&pattern[..i]);
+    if offset > 0.0 {
+        dash.push(offset);
+    }
+    if dash.len() % 2 == 1 {
+        dash.push(0.0);
+    }
+    // slivers left by rounding errors are zero-length entries
+    let eps = total * 1e-6;
+    for d in dash.iter_mut() {
+        if *d < eps {
+            *d = 0.0;
+        }
+    }
+    // `a, 0, b` draws the same as `a + b`
+    let mut merged: Vec<f32> = Vec::with_capacity(dash.len());
+    let mut i = 0;
+    while i < dash.len() {
+        if dash[i] == 0.0
+            && i + 1 < dash.len()
+            && let Some(prev) = merged.last_mut()
+        {
+            *prev += dash[i + 1];
+            i += 2;
+        } else {
+            merged.push(dash[i]);
+            i += 1;
+        }
+    }
+    // a trailing zero-length dash and gap draw nothing
+    while merged.len() > 2 && merged[merged.len() - 2..] == [0.0, 0.0] {
+        merged.truncate(merged.len() - 2);
+    }
+    style::Dash(merged)
+}
+
+const MARKER_SHAPES: &[&str] = &[
+    "circle",
+    "square",
+    "diamond",
+    "triangle",
+    "triangle-down",
+    "cross",
+    "plus",
+];
+
+pub fn extract_marker_shape(py_shape: &Bound<'_, PyAny>) -> PyResult<style::MarkerShape> {
+    let shape: &str = py_shape.extract()?;
+    match shape {
+        "circle" | "o" => Ok(style::MarkerShape::Circle),
+        "square" | "s" => Ok(style::MarkerShape::Square),
+        "diamond" | "D" => Ok(style::MarkerShape::Diamond),
+        "triangle" | "^" => Ok(style::MarkerShape::TriangleUp),
+        "triangle-down" | "v" => Ok(style::MarkerShape::TriangleDown),
+        "cross" | "x" => Ok(style::MarkerShape::Cross),
+        "plus" | "+" => Ok(style::MarkerShape::Plus),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Unknown marker shape: {} (supported shapes: {})",
+            shape,
+            MARKER_SHAPES.join(", ")
+        ))),
+    }
+}
+
+/// Colormaps sampled at evenly spaced points, from which colors are linearly interpolated.
+/// plotive has no colormap: they are only used to sample palettes.
+const COLORMAPS: &[(&str, &[&[u8]])] = &[
+    (
+        "viridis",
+        &[
+            b"#440154", b"#472d7b", b"#3b528b", b"#2c728e", b"#21918c", b"#28ae80", b"#5ec962",
+            b"#addc30", b"#fde725",
+        ],
+    ),
+    (
+        "magma",
+        &[
+            b"#000004", b"#1c1044", b"#4f127b", b"#812581", b"#b5367a", b"#e55964", b"#fb8761",
+            b"#fec287", b"#fcfdbf",
+        ],
+    ),
+    (
+        "plasma",
+        &[
+            b"#0d0887", b"#4c02a1", b"#7e03a8", b"#a92395", b"#cc4778", b"#e56b5d", b"#f89441",
+            b"#fdc328", b"#f0f921",
+        ],
+    ),
+    (
+        "inferno",
+        &[
+            b"#000004", b"#1f0c48", b"#550f6d", b"#88226a", b"#ba3655", b"#e35933", b"#f98e09",
+            b"#fbc72b", b"#fcffa4",
+        ],
+    ),
+    (
+        "cividis",
+        &[
+            b"#00204c", b"#01356e", b"#3d4a6c", b"#5b5e6f", b"#777776", b"#948e77", b"#b3a772",
+            b"#d4c164", b"#fee838",
+        ],
+    ),
+    (
+        "coolwarm",
+        &[
+            b"#3b4cc0", b"#6282ea", b"#8db0fe", b"#b8d0f9", b"#dddcdc", b"#f5c4ac", b"#f49a7b",
+            b"#de604d", b"#b40426",
+        ],
+    ),
+    (
+        "greys",
+        &[
+            b"#ffffff", b"#f0f0f0", b"#d9d9d9", b"#bdbdbd", b"#969696", b"#737373", b"#525252",
+            b"#252525", b"#000000",
+        ],
+    ),
+];
+
+/// A continuous colormap, defined by evenly spaced colors
+pub struct Colormap(Vec<ColorU8>);
+
+impl Colormap {
+    /// Linearly interpolated color at `t`, in the `[0, 1]` interval
+    pub fn color_at(&self, t: f32) -> ColorU8 {
+        let pos = t.clamp(0.0, 1.0) * (self.0.len() - 1) as f32;
+        let idx = (pos.floor() as usize).min(self.0.len() - 2);
+        let frac = pos - idx as f32;
+        let a = self.0[idx].rgba_f32();
+        let b = self.0[idx + 1].rgba_f32();
+        let lerp = |i: usize| a[i] + (b[i] - a[i]) * frac;
+        ColorU8::from_rgba_f32(lerp(0), lerp(1), lerp(2), lerp(3))
+    }
+}
+
+/// Extract a colormap, either by name or as a list of colors building a custom gradient.
+fn extract_colormap(py_cmap: &Bound<'_, PyAny>) -> PyResult<Colormap> {
+    if let Ok(name) = py_cmap.extract::<&str>() {
+        let name = if name == "grays" { "greys" } else { name };
+        return match COLORMAPS.iter().find(|(n, _)| *n == name) {
+            Some((_, colors)) => Ok(Colormap(
+                colors.iter().map(|c| ColorU8::from_html(c)).collect(),
+            )),
+            None => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown colormap: {} (supported colormaps: {})",
+                name,
+                COLORMAPS
+                    .iter()
+                    .map(|(n, _)| *n)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        };
+    }
+    if let Ok(py_list) = py_cmap.cast::<PyList>() {
+        if py_list.len() < 2 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Custom colormap must have at least 2 colors, got {}",
+                py_list.len()
+            )));
+        }
+        let mut colors = Vec::with_capacity(py_list.len());
+        for item in py_list.iter() {
+            colors.push(extract_color(&item)?);
         }
+        return Ok(Colormap(colors));
+    }
+    Err(pyo3::exceptions::PyTypeError::new_err(
+        "Colormap must be a string or a list of colors.",
+    ))
+}
+
+pub fn extract_opacity(py_opacity: &Bound<'_, PyAny>) -> PyResult<f32> {
+    let opacity = extract_f32_like(py_opacity)?;
+    if !(0.0..=1.0).contains(&opacity) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Opacity must be between 0.0 and 1.0, got {}",
+            opacity
+        )));
+    }
+    Ok(opacity)
+}
+
+pub fn extract_series_color(py_col: &Bound<'_, PyAny>) -> PyResult<style::series::Color> {
+    if let Ok(col) = py_col.extract::<&str>()
+        && col == "auto"
+    {
+        return Ok(style::series::Color::Auto);
     }
     let color = super::extract_color(py_col)?;
     Ok(color.into())
@@ -158,13 +534,20 @@ pub fn extract_theme_color(py_col: &Bound<'_, PyAny>) -> PyResult<style::theme::
 pub fn extract_theme_stroke(py_stroke: &Bound<'_, PyAny>) -> PyResult<style::theme::Stroke> {
     let py_color = py_stroke.getattr("color")?;
     if py_color.is_none() {
-        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+        return Err(pyo3::exceptions::PyValueError::new_err(
             "\"color\" attribute is required for stroke.",
-        )));
+        ));
     }
     let color = extract_theme_color(&py_color)?;
     let width = if let Some(w) = getattr_not_none(py_stroke, "width")? {
-        w.extract::<f32>()?
+        let width = extract_f32_like(&w)?;
+        if width < 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Stroke width must not be negative, got {}",
+                width
+            )));
+        }
+        width
     } else {
         1.0
     };
@@ -174,7 +557,7 @@ pub fn extract_theme_stroke(py_stroke: &Bound<'_, PyAny>) -> PyResult<style::the
         style::LinePattern::Solid
     };
     let opacity = if let Some(o) = getattr_not_none(py_stroke, "opacity")? {
-        Some(o.extract::<f32>()?)
+        Some(extract_opacity(&o)?)
     } else {
         None
     };
@@ -0,0 +1,199 @@
+//! Strict mode: report attributes of the figure definition that plotive does not know.
+//!
+//! Attributes are only read by name, so a misspelled attribute (e.g. `line.linewdith = 2`)
+//! is otherwise silently ignored. In strict mode, the attributes of every plotive object of
+//! the figure are checked once against the attributes its class accepts, before extraction.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyTuple};
+
+use super::extract_class_name;
+
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_strict(enabled: bool) {
+    STRICT.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_strict() -> bool {
+    STRICT.load(Ordering::Relaxed)
+}
+
+/// Check the attributes of the figure and of all the plotive objects it holds.
+/// No-op if strict mode is disabled.
+pub fn check_figure(py_fig: &Bound<'_, PyAny>) -> PyResult<()> {
+    if !is_strict() {
+        return Ok(());
+    }
+    let mut checker = Checker::default();
+    checker.check(py_fig)?;
+    if checker.unknown.is_empty() {
+        return Ok(());
+    }
+    checker.unknown.sort();
+    checker.unknown.dedup();
+    Err(pyo3::exceptions::PyAttributeError::new_err(format!(
+        "Strict mode: unknown attributes: {}. Check for misspelled attributes.",
+        checker.unknown.join(", ")
+    )))
+}
+
+#[derive(Default)]
+struct Checker {
+    /// Objects already checked, by address. The figure is borrowed for the whole check.
+    visited: HashSet<usize>,
+    /// Accepted attributes of each class, by type address.
+    accepted: HashMap<usize, HashSet<String>>,
+    unknown: Vec<String>,
+}
+
+impl Checker {
+    fn check(&mut self, obj: &Bound<'_, PyAny>) -> PyResult<()> {
+        if let Ok(list) = obj.cast::<PyList>() {
+            return self.check_items(list.iter());
+        }
+        if let Ok(tuple) = obj.cast::<PyTuple>() {
+            return self.check_items(tuple.iter());
+        }
+        if let Ok(dict) = obj.cast::<PyDict>() {
+            return self.check_items(dict.values().iter());
+        }
+        if !is_plotive_object(obj)? || !self.visited.insert(obj.as_ptr() as usize) {
+            return Ok(());
+        }
+        let Some(py_dict) = obj.getattr_opt("__dict__")? else {
+            return Ok(());
+        };
+        let Ok(py_dict) = py_dict.cast::<PyDict>() else {
+            return Ok(());
+        };
+        let ty = obj.get_type();
+        let ty_addr = ty.as_ptr() as usize;
+        if let std::collections::hash_map::Entry::Vacant(entry) = self.accepted.entry(ty_addr) {
+            entry.insert(accepted_attrs(&ty)?);
+        }
+        for (py_key, py_value) in py_dict.iter() {
+            let key: String = py_key.extract()?;
+            if !key.starts_with('_') && !self.accepted[&ty_addr].contains(&key) {
+                self.unknown
+                    .push(format!("{}.{}", extract_class_name(obj)?, key));
+            }
+            self.check(&py_value)?;
+        }
+        Ok(())
+    }
+
+    fn check_items<'py>(
+        &mut self,
+        mut items: impl Iterator<Item = Bound<'py, PyAny>>,
+    ) -> PyResult<()> {
+        // inline data columns can be long, and hold no plotive object
+        let Some(first) = items.next() else {
+            return Ok(());
+        };
+        if !is_container(&first) && !is_plotive_object(&first)? {
+            return Ok(());
+        }
+        self.check(&first)?;
+        for item in items {
+            self.check(&item)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_container(obj: &Bound<'_, PyAny>) -> bool {
+    obj.is_instance_of::<PyList>()
+        || obj.is_instance_of::<PyTuple>()
+        || obj.is_instance_of::<PyDict>()
+}
+
+fn is_plotive_object(obj: &Bound<'_, PyAny>) -> PyResult<bool> {
+    let module: String = obj.get_type().getattr("__module__")?.extract()?;
+    Ok(module == "plotive" || module.starts_with("plotive."))
+}
+
+/// Attributes accepted on instances of `ty`.
+/// These are the fields of dataclasses, and otherwise the constructor parameters
+/// and the attributes assigned to `self` by the constructors of the class and its bases.
+fn accepted_attrs(ty: &Bound<'_, pyo3::types::PyType>) -> PyResult<HashSet<String>> {
+    let py = ty.py();
+    let mut accepted = HashSet::new();
+    let dataclasses = py.import("dataclasses")?;
+    if dataclasses
+        .call_method1("is_dataclass", (ty,))?
+        .is_truthy()?
+    {
+        for field in dataclasses.call_method1("fields", (ty,))?.try_iter()? {
+            accepted.insert(field?.getattr("name")?.extract()?);
+        }
+        return Ok(accepted);
+    }
+    let sig = py.import("inspect")?.call_method1("signature", (ty,))?;
+    for name in sig.getattr("parameters")?.try_iter()? {
+        accepted.insert(name?.extract()?);
+    }
+    for base in ty.getattr("__mro__")?.try_iter()? {
+        let Some(init) = base?.getattr("__dict__")?.get_item("__init__").ok() else {
+            continue;
+        };
+        if init.getattr_opt("__code__")?.is_none() {
+            continue;
+        }
+        insert_self_stores(&init, &mut accepted)?;
+    }
+    Ok(accepted)
+}
+
+/// Insert the names of the attributes assigned as `self.<name>` in the source of `init`.
+/// Other names used by the constructor (functions, globals, attributes of other objects)
+/// are not attributes of the instance.
+fn insert_self_stores(init: &Bound<'_, PyAny>, accepted: &mut HashSet<String>) -> PyResult<()> {
+    let py = init.py();
+    // without source (e.g. compiled modules), only the parameters are accepted
+    let Ok(source) = py.import("inspect")?.call_method1("getsource", (init,)) else {
+        return Ok(());
+    };
+    let source = py.import("textwrap")?.call_method1("dedent", (source,))?;
+    let ast = py.import("ast")?;
+    let func = ast
+        .call_method1("parse", (source,))?
+        .getattr("body")?
+        .get_item(0)?;
+    let Ok(self_arg) = func.getattr("args")?.getattr("args")?.get_item(0) else {
+        return Ok(());
+    };
+    let self_name: String = self_arg.getattr("arg")?.extract()?;
+
+    for node in ast.call_method1("walk", (func,))?.try_iter()? {
+        let node = node?;
+        // `targets` of assignments, `target` of annotated and augmented assignments
+        let mut targets = Vec::new();
+        if let Some(py_targets) = node.getattr_opt("targets")? {
+            targets.extend(py_targets.try_iter()?.collect::<PyResult<Vec<_>>>()?);
+        }
+        if let Some(target) = node.getattr_opt("target")? {
+            targets.push(target);
+        }
+        while let Some(target) = targets.pop() {
+            // tuple unpacking, e.g. `self.x, self.y = xy`
+            if let Some(elts) = target.getattr_opt("elts")? {
+                targets.extend(elts.try_iter()?.collect::<PyResult<Vec<_>>>()?);
+                continue;
+            }
+            if extract_class_name(&target)? != "Attribute" {
+                continue;
+            }
+            let value = target.getattr("value")?;
+            if extract_class_name(&value)? == "Name"
+                && value.getattr("id")?.extract::<String>()? == self_name
+            {
+                accepted.insert(target.getattr("attr")?.extract()?);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Interactive figure window.
+//!
+//! `plotive_iced::Show` opens a window with default settings. Here, the plotive-iced
+//! `FigureShow` controller runs in its own iced application, to set the window title and size.
+
+use std::sync::Arc;
+
+use plotive::{data, drawing, fontdb};
+use plotive_iced::show::{Commands, FigureShow, Message};
+
+/// Options of the figure window.
+pub struct Params {
+    pub style: Option<plotive::Style>,
+    pub fontdb: Arc<fontdb::Database>,
+    /// Title of the window
+    pub title: String,
+    /// Initial size of the window. Defaults to the figure size.
+    pub size: Option<(f32, f32)>,
+    /// Whether the plots can be zoomed and panned
+    pub navigation: bool,
+}
+
+struct Viewer {
+    show: FigureShow<dyn data::Source>,
+    navigation: bool,
+}
+
+impl Viewer {
+    fn update(&mut self, msg: Message) -> iced::Task<Message> {
+        use iced::keyboard::{self, Key};
+
+        if let Message::Event(iced::Event::Keyboard(keyboard::Event::KeyPressed {
+            key,
+            modifiers,
+            ..
+        })) = &msg
+        {
+            match key.as_ref() {
+                Key::Character("r") if self.navigation && modifiers.is_empty() => {
+                    return self.show.update(Message::GoHome);
+                }
+                // exports the current view, through a save dialog
+                Key::Character("s") if modifiers.command() => {
+                    return self.show.update(Message::ExportPng);
+                }
+                _ => (),
+            }
+        }
+        // the toolbar buttons are hidden, but the wheel zoom and the middle button pan remain
+        let is_navigation = matches!(
+            msg,
+            Message::FigureMouseWheel(..)
+                | Message::FigureMousePress(_, iced::mouse::Button::Middle)
+        );
+        if is_navigation && !self.navigation {
+            return iced::Task::none();
+        }
+        self.show.update(msg)
+    }
+
+    fn view(&self) -> iced::Element<'_, Message> {
+        self.show.view()
+    }
+}
+
+/// Show a prepared figure in a window.
+/// This function blocks the calling thread until the window is closed.
+pub fn show(
+    fig: drawing::PreparedFigure,
+    data_source: Arc<dyn data::Source>,
+    params: Params,
+) -> iced::Result {
+    let (width, height) = params.size.unwrap_or_else(|| {
+        let size = fig.size();
+        (size.width(), size.height())
+    });
+    let Params {
+        style,
+        fontdb,
+        title,
+        navigation,
+        ..
+    } = params;
+    let commands = if navigation {
+        Commands::all()
+    } else {
+        Commands::all().without_view()
+    };
+
+    iced::application(
+        move || {
+            let mut show = FigureShow::new(fontdb.clone(), commands, None);
+            show.set_figure(fig.clone(), data_source.clone());
+            show.set_style(style.clone());
+            (Viewer { show, navigation }, iced::Task::none())
+        },
+        Viewer::update,
+        Viewer::view,
+    )
+    .title(move |_: &Viewer| title.clone())
+    .window_size(iced::Size::new(width, height))
+    .subscription(|_| iced::event::listen().map(Message::Event))
+    .run()
+}
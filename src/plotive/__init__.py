@@ -1,9 +1,12 @@
 """High-level public API for building and exporting Plotive figures."""
 
 from dataclasses import dataclass
+from collections.abc import Iterable
 from typing import TYPE_CHECKING
 
 if TYPE_CHECKING:
+    import numpy as np
+    from .color import Color
     from .style import Stroke, Fill, Style
 
 from .annot import Annotation
@@ -19,9 +22,40 @@ type Padding = float | tuple[float, float] | tuple[float, float, float, float]
 type DataSource = object
 """
 User-provided data source resolved at render time.
-Accepted objects are dictionaries of numpy arrays, dictionaries of lists, pandas DataFrames
+Accepted objects are dictionaries of numpy arrays, dictionaries of lists, lists of records
+(dictionaries sharing column keys), lists of columns or a single list of values and
+2D numpy arrays (columns are then named ``"0"``, ``"1"``, ...),
+pandas DataFrames, polars DataFrames and pyarrow Tables.
+The index of a pandas DataFrame is available as a column named after the index, or ``"index"``.
+Columns of dictionaries can also be ``LazyColumn`` objects, for data too large to fit in memory.
+Datetime columns of tz-aware pandas objects are read as their local times in their time zone,
+as plotive has no time zone support. Around daylight saving time changes, these local times
+may repeat or skip an hour.
 """
 
+
+@dataclass
+class LazyColumn:
+    """
+    Numeric data source column read by chunks, for arrays too large to fit in memory.
+
+    The values are pulled from ``source`` while the figure is rendered, one chunk at a time,
+    so that only one chunk is held in memory by plotive.
+    Every render reads the whole column, usually twice: the bounds and number of values
+    found by the first complete read are kept for the rest of the render.
+    If a chunk cannot be read, the render raises the error of the source once complete,
+    and no further chunk is read.
+    """
+
+    source: object
+    """
+    Array-like supporting ``len(source)`` and slicing as ``source[start:stop]``,
+    such as an h5py dataset or a zarr array.
+    Slices must be convertible to 1D float64 numpy arrays.
+    """
+    chunk_size: int = 65536
+    """Number of values read at once."""
+
 @dataclass(kw_only=True)
 class Legend:
     """Legend display settings."""
@@ -63,6 +97,9 @@ class Plot:
         title: None | str = None,
         legend: None | Legend | str = None,
         annotations: list[Annotation] = [],
+        spines: None | list[str] = None,
+        margin: None | float | tuple[float, float] = None,
+        stack_mode: str = "none",
     ):
         """Initialize a plot.
         By default, a plot has a single x and y axis, without any ticks, labels or grid.
@@ -71,6 +108,7 @@ class Plot:
         ----------
         series : list[Series]
             Data series to render.
+            May be empty for a plot containing only annotations.
         x_axis : Axis | None, default=None
             Convenience single x-axis.
         y_axis : Axis | None, default=None
@@ -80,19 +118,40 @@ class Plot:
         y_axes : list[Axis] | None, default=None
             Explicit list of y-axes.
         subplot : tuple[int, int] | None, default=None
-            Grid position of the subplot.
+            Grid position of the subplot as ``(row, col)``, starting at 1.
             Only relevant when multiple plots are defined in the same figure.
+            Plots must not share the same position.
         title : str | None, default=None
             Subplot title.
         legend : Legend | str | None, default=None
             Subplot legend config or shortcut position.
         annotations : list[Annotation], default=[]
             Annotation objects attached to this plot.
+            A plot without series nor annotations is rejected when rendering.
+        spines : list[str] | None, default=None
+            Spines of the plot frame to keep visible, among ``left``, ``right``,
+            ``top`` and ``bottom``. Only three sets are supported: all four sides, the
+            sides holding an axis (e.g. ``["left", "bottom"]`` for a plot with default
+            axes), or an empty list to hide the frame. If None, all spines are visible.
+        margin : float | tuple[float, float] | None, default=None
+            Space between the data and the plot frame, either for both directions or as
+            a ``(horizontal, vertical)`` tuple. The margin is in figure units, like the
+            figure size, and not a fraction of the data range. ``0`` makes the data touch
+            the frame. If None, the margin depends on the series drawn.
+        stack_mode : str, default="none"
+            How the ``HBar`` series of the plot combine. ``"none"`` draws each series
+            on its own, ``"stacked"`` stacks the bars of each category end to end, and
+            ``"percent"`` also scales each category to a total length of 100.
+            Stacked series must all be ``HBar`` series with the same categories, and
+            ``"percent"`` requires non-negative lengths.
 
         Raises
         ------
         ValueError
-            If both single-axis and multi-axis variants are provided.
+            If both single-axis and multi-axis variants are provided,
+            or if an unknown spine or stack mode is given.
+            A set of spines that is not supported, a negative margin or series that
+            cannot be stacked raise ``ValueError`` when rendering.
         """
         self.title = title
         self.subplot = subplot
@@ -102,6 +161,15 @@ class Plot:
         else:
             self.legend = legend
         self.annotations = annotations
+        if spines is not None:
+            for spine in spines:
+                if spine not in ("left", "right", "top", "bottom"):
+                    raise ValueError(f"Invalid spine: {spine}. Must be 'left', 'right', 'top' or 'bottom'.")
+        self.spines = spines
+        self.margin = margin
+        if stack_mode not in ("none", "stacked", "percent"):
+            raise ValueError(f"Invalid stack mode: {stack_mode}. Must be 'none', 'stacked' or 'percent'.")
+        self.stack_mode = stack_mode
 
         if x_axis is not None and x_axes is not None:
             raise ValueError("Cannot provide both 'x_axis' and 'x_axes'.")
@@ -136,9 +204,13 @@ class Figure:
         /,
         *,
         title: None | str = None,
+        title_fontsize: None | float = None,
+        title_color: None | Color = None,
         size: None | Size = (800, 600),
         padding: None | Padding = 20.0,
         fill: None | Fill = "background",
+        fill_opacity: None | float = None,
+        palette_cycle: bool = True,
         legend: None | Legend | str = None,
         plot: None | Plot = None,
         plots: None | list[Plot] = None,
@@ -149,12 +221,24 @@ class Figure:
         ----------
         title : str | None, default=None
             Figure title.
+        title_fontsize : float | None, default=None
+            Font size of the figure title. If None, the style default is used.
+        title_color : Color | None, default=None
+            Color of the figure title. If None, the style foreground color is used.
         size : Size | None, default=(800, 600)
             Output size in pixels.
         padding : Padding | None, default=20.0
             Figure inner padding.
         fill : Fill | None, default="background"
             Figure background fill.
+        fill_opacity : float | None, default=None
+            Opacity of the figure fill, in the ``[0, 1]`` interval.
+            Useful to draw a semi-transparent background over an image.
+        palette_cycle : bool, default=True
+            Whether the style palette repeats when a plot has more series than the palette
+            has colors. A series takes the palette color at its position in the plot, sorted
+            by ``zorder``. If False, a series with an automatic color beyond the end of the
+            palette raises a ``ValueError`` when rendering, before anything is drawn.
         legend : Legend | str | None, default=None
             Figure-level legend config or shortcut position.
         plot : Plot | None, default=None
@@ -175,21 +259,49 @@ class Figure:
             raise ValueError("Either 'plot' or 'plots' must be provided.")
 
         self.title = title
+        self.title_fontsize = title_fontsize
+        self.title_color = title_color
         self.size = size
         self.padding = padding
         self.fill = fill
+        self.fill_opacity = fill_opacity
+        self.palette_cycle = palette_cycle
         if isinstance(legend, str):
             self.legend = Legend(pos=legend)
         else:
             self.legend = legend
 
+    def validate(self, *, data_source: None | DataSource = None):
+        """Check the figure definition without rendering it.
+
+        The same errors as during export are raised, e.g. for bad axis references,
+        unknown names or mismatched subplot grids.
+        The inline ``x`` and ``y`` data columns of a series (lists or arrays) must have the
+        same length. These are the only columns of a series that are compared: histograms
+        have a single column, and scatter marker sizes and colors apply to all the markers.
+        Lengths of columns referenced in the data source are only checked while rendering.
+        Columns referenced by name must exist in ``data_source``, otherwise a ``KeyError``
+        listing the available columns is raised.
+
+        Parameters
+        ----------
+        data_source : DataSource | None, default=None
+            Runtime data source.
+        """
+        from ._rs import validate as rs_validate
+
+        rs_validate(self, data_source)
+
     def save_png(
         self,
         path: str,
         *,
         data_source: None | DataSource = None,
         style: None | Style | str = None,
-    ):
+        width: None | float = None,
+        height: None | float = None,
+        scale: None | float = None,
+    ) -> tuple[int, int]:
         """Export the figure as PNG.
 
         Parameters
@@ -200,10 +312,145 @@ class Figure:
             Runtime data source.
         style : Style | str | None, default=None
             Rendering style object or style name.
+        width : float | None, default=None
+            Override the figure width.
+        height : float | None, default=None
+            Override the figure height.
+        scale : float | None, default=None
+            Scale factor from figure units to pixels.
+
+        Returns
+        -------
+        tuple[int, int]
+            Size of the written image in pixels, as ``(width, height)``.
         """
         from ._rs import save_png as rs_save_png
 
-        rs_save_png(self, path, data_source, style)
+        return rs_save_png(
+            self, path, data_source, style, self._override_size(width, height), scale
+        )
+
+    def save_subplots_png(
+        self,
+        path_template: str,
+        *,
+        data_source: None | DataSource = None,
+        style: None | Style | str = None,
+    ):
+        """Export each plot of the figure as a separate PNG file.
+
+        Each plot is rendered alone, with the size and fill of the figure.
+        The plots are rendered in parallel, on all the available CPU cores.
+
+        Parameters
+        ----------
+        path_template : str
+            Output file path, where ``{}`` is replaced by the plot number, starting at 1.
+            The placeholder can be omitted if the figure has a single plot.
+        data_source : DataSource | None, default=None
+            Runtime data source.
+        style : Style | str | None, default=None
+            Rendering style object or style name.
+
+        Raises
+        ------
+        ValueError
+            If the figure has several plots and ``path_template`` has no placeholder.
+        """
+        from ._rs import save_subplots_png as rs_save_subplots_png
+
+        rs_save_subplots_png(self, path_template, data_source, style)
+
+    def to_png_bytes(
+        self,
+        *,
+        data_source: None | DataSource = None,
+        style: None | Style | str = None,
+        width: None | float = None,
+        height: None | float = None,
+        scale: None | float = None,
+    ) -> bytes:
+        """Render the figure to PNG encoded bytes.
+
+        Parameters
+        ----------
+        data_source : DataSource | None, default=None
+            Runtime data source.
+        style : Style | str | None, default=None
+            Rendering style object or style name.
+        width : float | None, default=None
+            Override the figure width.
+        height : float | None, default=None
+            Override the figure height.
+        scale : float | None, default=None
+            Scale factor from figure units to pixels.
+
+        Returns
+        -------
+        bytes
+            The PNG encoded image.
+        """
+        from ._rs import to_png_bytes as rs_to_png_bytes
+
+        return rs_to_png_bytes(
+            self, data_source, style, self._override_size(width, height), scale
+        )
+
+    def to_rgba_array(
+        self,
+        *,
+        data_source: None | DataSource = None,
+        style: None | Style | str = None,
+        width: None | float = None,
+        height: None | float = None,
+        scale: None | float = None,
+    ) -> np.ndarray:
+        """Render the figure to a numpy RGBA image.
+
+        Parameters
+        ----------
+        data_source : DataSource | None, default=None
+            Runtime data source.
+        style : Style | str | None, default=None
+            Rendering style object or style name.
+        width : float | None, default=None
+            Override the figure width.
+        height : float | None, default=None
+            Override the figure height.
+        scale : float | None, default=None
+            Scale factor from figure units to pixels.
+
+        Returns
+        -------
+        np.ndarray
+            A ``(height, width, 4)`` array of ``uint8``, with non-premultiplied alpha.
+        """
+        from ._rs import to_rgba_array as rs_to_rgba_array
+
+        return rs_to_rgba_array(
+            self, data_source, style, self._override_size(width, height), scale
+        )
+
+    def _repr_png_(self) -> bytes:
+        """Render the figure for inline display in Jupyter notebooks.
+
+        The figure is rendered at its configured size, with the default style and without
+        data source. Figures referencing a data source should be displayed with
+        ``IPython.display.Image(fig.to_png_bytes(data_source=...))``.
+        """
+        return self.to_png_bytes()
+
+    def _override_size(
+        self, width: None | float, height: None | float
+    ) -> None | tuple[float, float]:
+        """Return the figure size with optional width and height overrides."""
+        if width is None and height is None:
+            return None
+        (w, h) = self.size if self.size is not None else (800, 600)
+        return (
+            width if width is not None else w,
+            height if height is not None else h,
+        )
 
     def save_svg(
         self,
@@ -228,17 +475,251 @@ class Figure:
         rs_save_svg(self, path, data_source, style)
 
     def show(
-        self, *, data_source: None | DataSource = None, style: None | Style | str = None
+        self,
+        *,
+        data_source: None | DataSource = None,
+        style: None | Style | str = None,
+        title: None | str = None,
+        width: None | float = None,
+        height: None | float = None,
+        navigation: bool = True,
     ):
         """Display the figure in an interactive viewer.
 
+        The toolbar exports the current view, including the zoomed and panned axis ranges,
+        to PNG, SVG or the clipboard. Ctrl+S exports it to PNG through a save dialog.
+
         Parameters
         ----------
         data_source : DataSource | None, default=None
             Runtime data source.
         style : Style | str | None, default=None
             Rendering style object or style name.
+        title : str | None, default=None
+            Window title. Defaults to the figure title.
+        width : float | None, default=None
+            Initial window width. Defaults to the figure width.
+        height : float | None, default=None
+            Initial window height. Defaults to the figure height.
+        navigation : bool, default=True
+            Whether the plots can be zoomed and panned.
+            The plots are zoomed with the mouse wheel or by selecting a rectangle, and panned
+            by dragging with the middle button or with the pan tool of the toolbar.
+            Press ``r`` or the home button to reset the original view.
         """
         from ._rs import show as rs_show
 
-        rs_show(self, data_source, style)
+        if title is None:
+            title = self.title
+        rs_show(
+            self,
+            data_source,
+            style,
+            title,
+            self._override_size(width, height),
+            navigation,
+        )
+
+
+class Renderer:
+    """Raster renderer holding export options, to render many figures.
+
+    The style and options are prepared once at construction, instead of at each
+    export as with ``Figure.save_png``, which speeds up batch exports and dashboards
+    and sets the common options in a single place.
+    """
+
+    def __init__(
+        self,
+        *,
+        style: None | Style | str = None,
+        width: None | float = None,
+        height: None | float = None,
+        scale: None | float = None,
+    ):
+        """Initialize a renderer.
+
+        Parameters
+        ----------
+        style : Style | str | None, default=None
+            Rendering style object or style name.
+        width : float | None, default=None
+            Override the width of the rendered figures.
+        height : float | None, default=None
+            Override the height of the rendered figures.
+        scale : float | None, default=None
+            Scale factor from figure units to pixels.
+            There is no dpi setting: a scale of 2 gives the pixel density that
+            a dpi twice as high would.
+
+        Raises
+        ------
+        ValueError
+            If ``style`` is invalid.
+        """
+        from ._rs import Renderer as RsRenderer
+
+        self.width = width
+        self.height = height
+        self._rs = RsRenderer(style, scale)
+
+    def save_png(
+        self,
+        figure: Figure,
+        path: str,
+        *,
+        data_source: None | DataSource = None,
+    ) -> tuple[int, int]:
+        """Export a figure as PNG.
+
+        Parameters
+        ----------
+        figure : Figure
+            Figure to render.
+        path : str
+            Output file path.
+        data_source : DataSource | None, default=None
+            Runtime data source.
+
+        Returns
+        -------
+        tuple[int, int]
+            Size of the written image in pixels, as ``(width, height)``.
+        """
+        size = figure._override_size(self.width, self.height)
+        return self._rs.save_png(figure, path, data_source, size)
+
+    def to_bytes(
+        self,
+        figure: Figure,
+        *,
+        data_source: None | DataSource = None,
+    ) -> bytes:
+        """Render a figure to PNG encoded bytes.
+
+        Parameters
+        ----------
+        figure : Figure
+            Figure to render.
+        data_source : DataSource | None, default=None
+            Runtime data source.
+
+        Returns
+        -------
+        bytes
+            PNG encoded image.
+        """
+        size = figure._override_size(self.width, self.height)
+        return self._rs.to_png_bytes(figure, data_source, size)
+
+    def to_png_bytes(
+        self,
+        figure: Figure,
+        *,
+        data_source: None | DataSource = None,
+    ) -> bytes:
+        """Alias of ``to_bytes``, named like ``Figure.to_png_bytes``."""
+        return self.to_bytes(figure, data_source=data_source)
+
+
+def save_animation(
+    figures: Iterable[Figure],
+    path: str,
+    *,
+    fps: float = 10,
+    data_source: None | DataSource = None,
+    style: None | Style | str = None,
+    repeat: bool = True,
+):
+    """Render a sequence of figures as the frames of an animation.
+
+    The format is deduced from the extension of ``path``: animated PNG for ``.png``
+    and ``.apng``, GIF for ``.gif``. GIF colors are quantized to 256 colors per frame
+    and transparency is either full or none.
+
+    Parameters
+    ----------
+    figures : Iterable[Figure]
+        Frames of the animation, in order. All frames must have the same size.
+    path : str
+        Output file path.
+    fps : float, default=10
+        Number of frames per second.
+    data_source : DataSource | None, default=None
+        Runtime data source, shared by all the frames.
+    style : Style | str | None, default=None
+        Rendering style object or style name.
+    repeat : bool, default=True
+        Whether the animation loops forever. If False, it is played once.
+
+    Raises
+    ------
+    ValueError
+        If the extension of ``path`` is not supported, if ``fps`` is not positive,
+        if ``figures`` is empty or if the frames have different sizes.
+    """
+    from ._rs import save_animation as rs_save_animation
+
+    rs_save_animation(figures, path, data_source, style, fps, repeat)
+
+
+def set_strict(enabled: bool = True):
+    """Enable or disable strict mode.
+
+    In strict mode, exporting, showing or validating a figure raises an ``AttributeError``
+    listing the attributes of the figure definition that plotive does not know,
+    such as misspelled attributes set after construction (e.g. ``line.linewdith = 2``).
+    Each object of the figure is checked once, before the figure is extracted.
+    Strict mode is disabled by default.
+
+    Parameters
+    ----------
+    enabled : bool, default=True
+        Whether strict mode is enabled.
+    """
+    from ._rs import set_strict as rs_set_strict
+
+    rs_set_strict(enabled)
+
+
+def is_strict() -> bool:
+    """Return whether strict mode is enabled. See ``set_strict``."""
+    from ._rs import is_strict as rs_is_strict
+
+    return rs_is_strict()
+
+
+def set_column_cache(enabled: bool = True):
+    """Enable or disable the cache of converted data columns.
+
+    Data columns that are not numpy arrays of a natively supported type, such as pandas
+    series, are converted to numpy arrays each time a figure is rendered. With the cache
+    enabled, converted columns are kept and reused by the next renders, which speeds up
+    loops rendering the same data repeatedly, e.g. animation frames where only some
+    columns change. The cache is disabled by default.
+
+    Cached columns are identified by the address, dtype, shape and strides of their numpy
+    buffer. The cache keeps a reference to these numpy arrays, so that their buffer address
+    cannot be reused by another array while cached. The values are not compared: modifying
+    a column in place is not detected, and the next renders use the stale converted values.
+    Call ``clear_column_cache`` after such a change, or pass a new array instead.
+
+    Only columns backed by a numpy array are cached. Lists, categorical and nullable
+    integer columns are converted at each render. The cache holds at most 64 columns
+    and is cleared when full. Disabling the cache clears it.
+
+    Parameters
+    ----------
+    enabled : bool, default=True
+        Whether the column cache is enabled.
+    """
+    from ._rs import set_column_cache as rs_set_column_cache
+
+    rs_set_column_cache(enabled)
+
+
+def clear_column_cache():
+    """Drop the converted columns kept by the column cache. See ``set_column_cache``."""
+    from ._rs import clear_column_cache as rs_clear_column_cache
+
+    rs_clear_column_cache()
@@ -38,7 +38,10 @@ class Scale:
         return SharedScale(ref)
 
 class AutoScale(Scale):
-    """Scale whose bounds are fully determined automatically."""
+    """Scale whose bounds are fully determined automatically.
+
+    String data is placed on a categorical axis, in order of appearance.
+    """
 
     pass
 
@@ -371,6 +374,8 @@ class Axis:
         grid: Stroke | str | None = None,
         minor_ticks: TicksLocator | str | None = None,
         minor_grid: Stroke | str | None = None,
+        twin: str | None = None,
+        tick_fontsize: float | None = None,
     ):
         """Initialize an axis and normalize rendering options.
 
@@ -387,13 +392,24 @@ class Axis:
         side : str | None, default=None
             Explicit side: ``left``, ``right``, ``top``, or ``bottom``.
         ticks : Ticks | str | None, default=None
-            Major tick configuration.
+            Major tick configuration. If None and a series draws a datetime column
+            of the data source along this axis, datetime ticks are used.
         grid : Stroke | str | None, default=None
-            Major grid style.
+            Major grid style. A string is either ``"auto"`` for the theme grid color,
+            ``"none"`` to explicitly disable the grid, or a color.
         minor_ticks : TicksLocator | str | None, default=None
             Minor tick locator.
         minor_grid : Stroke | str | None, default=None
-            Minor grid style.
+            Minor grid style. Strings are handled as for ``grid``, with a dashed pattern.
+            Unless specified, the minor grid width is half the width of the major grid.
+        twin : str | None, default=None
+            Id of another axis of the same dimension that this axis is the twin of.
+            Unless ``side`` or ``opposite_side`` is given, the twin axis is placed on the
+            side opposite to the referenced axis. The twin axis keeps its own scale, so
+            series can reference it through ``y_axis`` (or ``x_axis``) and be scaled
+            independently.
+        tick_fontsize : float | None, default=None
+            Font size of the tick labels. If None, the default size is used.
 
         Raises
         ------
@@ -424,8 +440,13 @@ class Axis:
             self.opposite_side = (side == "right" or side == "top")
         elif opposite_side is not None:
             self.opposite_side = opposite_side
+        elif twin is not None:
+            # resolved against the referenced axis at render time
+            self.opposite_side = None
         else:
             self.opposite_side = False
+        self.twin = twin
+        self.tick_fontsize = tick_fontsize
 
         if isinstance(ticks, str):
             self.ticks = Ticks(locator=_get_ticks_locator(ticks))
@@ -435,7 +456,9 @@ class Axis:
             self.ticks = None
 
         if isinstance(grid, str):
-            if grid.lower() == "auto":
+            if grid.lower() == "none":
+                self.grid = "none"
+            elif grid.lower() == "auto":
                 self.grid = Stroke(color="grid")
             else:
                 self.grid = Stroke(color=grid)
@@ -448,9 +471,11 @@ class Axis:
             self.minor_ticks = None
 
         if isinstance(minor_grid, str):
-            if minor_grid.lower() == "auto":
-                self.minor_grid = Stroke(color="grid", width=0.5, pattern=[5, 5])
+            if minor_grid.lower() == "none":
+                self.minor_grid = "none"
+            elif minor_grid.lower() == "auto":
+                self.minor_grid = Stroke(color="grid", pattern=[5, 5])
             else:
-                self.minor_grid = Stroke(color=minor_grid, width=0.5, pattern=[5, 5])
+                self.minor_grid = Stroke(color=minor_grid, pattern=[5, 5])
         else:
             self.minor_grid = minor_grid
@@ -4,6 +4,9 @@
 type Color = str | tuple[int, int, int] | tuple[int, int, int, float]
 """
 Named/CSS color string or RGB(A) tuple. (note the alpha component is a float in [0, 1])
+All CSS named colors (e.g. "tomato", "steelblue") are accepted, case-insensitively.
+Strings can also be 8-digit ``#RRGGBBAA`` hex, or ``hsl(h, s%, l%)`` and ``hsla(h, s%, l%, a)``
+(the CSS space separated form ``hsl(h s% l% / a)`` is accepted as well).
 When used in the context of a themable element (text, axis, grid etc.),
 the string can also be a reference to a theme palette color by name.
 Accepted theme colors are "background", "foreground", "grid", "legend-fill" and "legend-border".
@@ -14,7 +14,10 @@ type AxisRef = str | int
 """Axis reference by string identifier or numeric index."""
 
 class Series(ABC):
-    """Base class for plot series objects."""
+    """Base class for plot series objects.
+
+    Series are drawn clipped to the plot area.
+    """
 
     def __init__(
         self,
@@ -22,6 +25,8 @@ class Series(ABC):
         name: None | str = None,
         x_axis: None | AxisRef = None,
         y_axis: None | AxisRef = None,
+        zorder: int = 0,
+        show_in_legend: bool = True,
     ):
         """Initialize common series metadata.
 
@@ -33,10 +38,18 @@ class Series(ABC):
             Target x-axis reference.
         y_axis : AxisRef | None, default=None
             Target y-axis reference.
+        zorder : int, default=0
+            Drawing order within the plot. Series with higher ``zorder`` are drawn on top.
+            Series with equal ``zorder`` are drawn in insertion order.
+        show_in_legend : bool, default=True
+            Whether the series has an entry in the legend.
+            Hidden series are still drawn, which is useful for reference or helper lines.
         """
         self.name = name
         self.x_axis = x_axis
         self.y_axis = y_axis
+        self.zorder = zorder
+        self.show_in_legend = show_in_legend
 
     def _get_type(self) -> str:
         """Return the concrete series type name."""
@@ -44,35 +57,49 @@ class Series(ABC):
 
 
 class Line(Series):
-    """Line series defined by x/y coordinates."""
+    """Line series defined by x/y coordinates.
+
+    As with matplotlib's ``plot(y)``, a line can be given only its y values,
+    in which case x defaults to the indices ``0, 1, ..., len(y) - 1``.
+    """
 
     def __init__(
         self,
-        x: DataCol,
-        y: DataCol,
+        x: None | DataCol = None,
+        y: None | DataCol = None,
         *,
         name: None | str = None,
         x_axis: None | AxisRef = None,
         y_axis: None | AxisRef = None,
+        zorder: int = 0,
+        show_in_legend: bool = True,
         linewidth: None | float = None,
         linestyle: None | str | list[float] = None,
         color: None | Color = None,
         interpolation: None | str = None,
+        max_points: None | int = None,
     ):
         """Initialize a line series.
 
         Parameters
         ----------
-        x : DataCol
+        x : DataCol | None, default=None
             X values or x data source reference.
-        y : DataCol
+            If ``y`` is omitted, this is taken as the y values instead.
+            If None, x defaults to the indices of the y values.
+        y : DataCol | None, default=None
             Y values or y data source reference.
         name : str | None, default=None
             Legend/display name of the series.
+            If None and ``y`` is a data source reference, the column name is used.
         x_axis : AxisRef | None, default=None
             Target x-axis reference.
         y_axis : AxisRef | None, default=None
             Target y-axis reference.
+        zorder : int, default=0
+            Drawing order within the plot. Higher values are drawn on top.
+        show_in_legend : bool, default=True
+            Whether the series has an entry in the legend. The series is drawn either way.
         linewidth : float | None, default=None
             Override line width.
         linestyle : str | list[float] | None, default=None
@@ -81,11 +108,243 @@ class Line(Series):
             Line color.
         interpolation : str | None, default=None
             Interpolation mode for rendering.
+        max_points : int | None, default=None
+            Maximum number of points drawn. A longer line is decimated by keeping, for each
+            group of consecutive points, those with the minimum and maximum y values.
+            This preserves the visual envelope of the line while drastically cutting the
+            rendering time of large series, e.g. high-frequency signals.
+            The x values are expected to be sorted. Gaps from missing or NaN values are
+            kept, and each run between gaps is decimated separately, so a line with many gaps
+            may keep more points. Lines with non-numeric columns are not decimated.
+            Must be at least 4. If None, all points are drawn.
+
+        Raises
+        ------
+        ValueError
+            If neither ``x`` nor ``y`` is provided.
         """
-        super().__init__(name=name, x_axis=x_axis, y_axis=y_axis)
+        super().__init__(
+            name=name, x_axis=x_axis, y_axis=y_axis, zorder=zorder,
+            show_in_legend=show_in_legend,
+        )
+        if x is None and y is None:
+            raise ValueError("Line requires y values, given as Line(y) or Line(x, y).")
+        if y is None:
+            x, y = None, x
         self.x = x
         self.y = y
         self.linewidth = linewidth
         self.linestyle = linestyle
         self.color = color
         self.interpolation = interpolation
+        self.max_points = max_points
+
+
+class Step(Series):
+    """Step line series, the equivalent of matplotlib's ``step()``."""
+
+    def __init__(
+        self,
+        x: DataCol,
+        y: DataCol,
+        *,
+        where: str = "pre",
+        name: None | str = None,
+        x_axis: None | AxisRef = None,
+        y_axis: None | AxisRef = None,
+        zorder: int = 0,
+        show_in_legend: bool = True,
+        linewidth: None | float = None,
+        linestyle: None | str | list[float] = None,
+        color: None | Color = None,
+    ):
+        """Initialize a step series.
+
+        Parameters
+        ----------
+        x : DataCol
+            X values or x data source reference.
+        y : DataCol
+            Y values or y data source reference.
+        where : str, default="pre"
+            Position of the steps: ``pre`` (the step occurs before the x value),
+            ``post`` (the step occurs after the x value) or ``mid`` (half-way between x values).
+        name : str | None, default=None
+            Legend/display name of the series.
+        x_axis : AxisRef | None, default=None
+            Target x-axis reference.
+        y_axis : AxisRef | None, default=None
+            Target y-axis reference.
+        zorder : int, default=0
+            Drawing order within the plot. Higher values are drawn on top.
+        show_in_legend : bool, default=True
+            Whether the series has an entry in the legend. The series is drawn either way.
+        linewidth : float | None, default=None
+            Override line width.
+        linestyle : str | list[float] | None, default=None
+            Line style or dash pattern.
+        color : Color | None, default=None
+            Line color.
+        """
+        super().__init__(
+            name=name, x_axis=x_axis, y_axis=y_axis, zorder=zorder,
+            show_in_legend=show_in_legend,
+        )
+        self.x = x
+        self.y = y
+        self.where = where
+        self.linewidth = linewidth
+        self.linestyle = linestyle
+        self.color = color
+
+
+class Scatter(Series):
+    """Scatter series drawing a marker at each x/y point."""
+
+    def __init__(
+        self,
+        x: DataCol,
+        y: DataCol,
+        *,
+        name: None | str = None,
+        x_axis: None | AxisRef = None,
+        y_axis: None | AxisRef = None,
+        zorder: int = 0,
+        show_in_legend: bool = True,
+        marker: None | str = None,
+        size: None | float = None,
+        color: None | Color = None,
+    ):
+        """Initialize a scatter series.
+
+        Parameters
+        ----------
+        x : DataCol
+            X values or x data source reference.
+        y : DataCol
+            Y values or y data source reference.
+        name : str | None, default=None
+            Legend/display name of the series.
+        x_axis : AxisRef | None, default=None
+            Target x-axis reference.
+        y_axis : AxisRef | None, default=None
+            Target y-axis reference.
+        zorder : int, default=0
+            Drawing order within the plot. Higher values are drawn on top.
+        show_in_legend : bool, default=True
+            Whether the series has an entry in the legend. The series is drawn either way.
+        marker : str | None, default=None
+            Marker shape: ``circle``, ``square``, ``diamond``, ``triangle``,
+            ``triangle-down``, ``cross`` or ``plus``. If None, circles are drawn.
+            Overlay a ``Scatter`` on a ``Line`` with the same data to get a line with markers.
+        size : float | None, default=None
+            Marker size in pixels, applied to all the markers.
+            If None, the default marker size is used.
+        color : Color | None, default=None
+            Marker fill color, applied to all the markers.
+        """
+        super().__init__(
+            name=name, x_axis=x_axis, y_axis=y_axis, zorder=zorder,
+            show_in_legend=show_in_legend,
+        )
+        self.x = x
+        self.y = y
+        self.marker = marker
+        self.size = size
+        self.color = color
+
+
+class HBar(Series):
+    """Horizontal bar series where bars are positioned along y and extend along x.
+
+    Bars are drawn on the primary axes of the plot and always extend from ``x = 0``.
+    """
+
+    def __init__(
+        self,
+        x: DataCol,
+        y: DataCol,
+        *,
+        name: None | str = None,
+        zorder: int = 0,
+        show_in_legend: bool = True,
+        height: None | float = None,
+        color: None | Color = None,
+    ):
+        """Initialize a horizontal bar series.
+
+        Parameters
+        ----------
+        x : DataCol
+            Numeric bar lengths or x data source reference.
+        y : DataCol
+            Bar categories (strings) or y data source reference to a string column.
+            Categories are placed at evenly spaced positions along the y-axis.
+        name : str | None, default=None
+            Legend/display name of the series.
+        zorder : int, default=0
+            Drawing order within the plot. Higher values are drawn on top.
+        show_in_legend : bool, default=True
+            Whether the series has an entry in the legend. The series is drawn either way.
+        height : float | None, default=None
+            Bar thickness as a fraction of the category spacing, in the ``(0, 1]`` interval.
+            Bars are centered on their category.
+        color : Color | None, default=None
+            Bar fill color.
+        """
+        super().__init__(name=name, zorder=zorder, show_in_legend=show_in_legend)
+        self.x = x
+        self.y = y
+        self.height = height
+        self.color = color
+
+
+class Histogram(Series):
+    """Histogram series computed from raw samples."""
+
+    def __init__(
+        self,
+        values: DataCol,
+        *,
+        name: None | str = None,
+        x_axis: None | AxisRef = None,
+        y_axis: None | AxisRef = None,
+        zorder: int = 0,
+        show_in_legend: bool = True,
+        bins: None | int = None,
+        density: bool = False,
+        color: None | Color = None,
+    ):
+        """Initialize a histogram series.
+
+        Parameters
+        ----------
+        values : DataCol
+            Samples or samples data source reference.
+        name : str | None, default=None
+            Legend/display name of the series.
+        x_axis : AxisRef | None, default=None
+            Target x-axis reference.
+        y_axis : AxisRef | None, default=None
+            Target y-axis reference.
+        zorder : int, default=0
+            Drawing order within the plot. Higher values are drawn on top.
+        show_in_legend : bool, default=True
+            Whether the series has an entry in the legend. The series is drawn either way.
+        bins : int | None, default=None
+            Number of uniform bins spanning the data min/max.
+            If None, 10 bins are used. Explicit bin edges are not supported,
+            as plotive only computes uniform bins.
+        density : bool, default=False
+            Normalize the histogram so that its total area is 1.
+        color : Color | None, default=None
+            Bars fill color.
+        """
+        super().__init__(
+            name=name, x_axis=x_axis, y_axis=y_axis, zorder=zorder,
+            show_in_legend=show_in_legend,
+        )
+        self.values = values
+        self.bins = bins
+        self.density = density
+        self.color = color
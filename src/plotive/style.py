@@ -13,10 +13,18 @@ class Stroke:
     ----------
     color : Color
         Stroke color.
-    width : float, default=1.0
-        Stroke width in pixels.
-    pattern : list[float] | str | None, default=None
-        Dash pattern specification.
+    width : float | None, default=None
+        Stroke width in pixels. If None, a width of 1.0 is used, except for minor grids
+        where the width is half the width of the major grid.
+    pattern : list[float] | str | tuple[list[float] | str, float] | None, default=None
+        Dash pattern specification. Either ``"solid"``, ``"dashed"``, ``"dotted"``,
+        or a list of positive dash and gap lengths.
+        Lengths are relative to the stroke width. Zero, negative or non-finite lengths,
+        as well as an empty list, raise ``ValueError`` when rendering.
+        A ``(pattern, offset)`` tuple shifts a dashed or dotted pattern by ``offset``,
+        in the same units as the pattern, e.g. to keep the dashes of stacked lines
+        from aligning. The offset can be any finite value, and wraps around the pattern
+        length; a negative offset shifts the pattern the other way.
     opacity : float, default=1.0
         Stroke opacity in the ``[0, 1]`` interval.
     """
@@ -25,8 +33,8 @@ class Stroke:
         self,
         *,
         color: Color,
-        width: float = 1.0,
-        pattern: None | list[float] | str = None,
+        width: float | None = None,
+        pattern: None | list[float] | str | tuple[list[float] | str, float] = None,
         opacity: float = 1.0,
     ):
         """Initialize a stroke style."""
@@ -73,8 +81,12 @@ class ThemePalette:
 type Theme = ThemePalette | str
 """Explicit theme object or predefined theme name."""
 
-type SeriesPalette = list[Color] | str
-"""Explicit series palette or predefined palette name."""
+type SeriesPalette = list[Color] | str | dict
+"""Explicit series palette, predefined palette name, or colormap sampling.
+
+A colormap sampling is a dict such as ``{"colormap": "viridis", "n": 6}``,
+producing ``n`` colors evenly sampled from the colormap, ends included.
+"""
 
 
 class Style:
@@ -97,3 +109,24 @@ class Style:
         """Initialize global style settings."""
         self.theme = theme
         self.palette = palette
+
+
+def available_styles() -> list[str]:
+    """Return the names of the predefined styles, including aliases."""
+    from ._rs import available_styles as rs_available_styles
+
+    return rs_available_styles()
+
+
+def available_themes() -> list[str]:
+    """Return the names of the predefined themes, including aliases."""
+    from ._rs import available_themes as rs_available_themes
+
+    return rs_available_themes()
+
+
+def available_palettes() -> list[str]:
+    """Return the names of the predefined series palettes, including aliases."""
+    from ._rs import available_palettes as rs_available_palettes
+
+    return rs_available_palettes()
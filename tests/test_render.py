@@ -0,0 +1,459 @@
+import numpy as np
+import pytest
+
+import plotive as pv
+
+PNG_SIGNATURE = b"\x89PNG\r\n\x1a\n"
+
+
+def render(tmp_path, series, data_source=None, **plot_args):
+    fig = pv.Figure(size=(320, 240), plot=pv.Plot(series=series, **plot_args))
+    path = tmp_path / "figure.png"
+    fig.save_png(str(path), data_source=data_source)
+    return path.read_bytes()
+
+
+@pytest.mark.parametrize(
+    "series",
+    [
+        pv.series.Line([0, 1, 2, 3], [1, 3, 2, 4], linestyle="dashed", linewidth=2),
+        pv.series.Line([1, 3, 2, 4]),
+        pv.series.Step([0, 1, 2, 3], [1, 3, 2, 4], where="post"),
+        pv.series.Scatter([0, 1, 2, 3], [1, 3, 2, 4], size=6, color="red"),
+        pv.series.HBar([3, 5, 2], ["a", "b", "c"]),
+        pv.series.Histogram([0.1, 0.4, 0.5, 0.9, 1.2, 1.3], bins=4, density=True),
+    ],
+    ids=["line", "line-y-only", "step", "scatter", "hbar", "histogram"],
+)
+def test_series(tmp_path, series):
+    assert render(tmp_path, [series]).startswith(PNG_SIGNATURE)
+
+
+def test_hbar_rejects_categorical_x(tmp_path):
+    fig = pv.Figure(plot=pv.Plot(series=[pv.series.HBar(["a", "b"], [1, 2])]))
+    with pytest.raises(ValueError, match="numeric 'x' values"):
+        fig.save_png(str(tmp_path / "hbar.png"))
+
+
+def test_hbar_rejects_numeric_y_column(tmp_path):
+    series = [pv.series.HBar("x", "y")]
+    with pytest.raises(ValueError, match="column 'y' does not hold strings"):
+        render(tmp_path, series, data_source={"x": [3, 5], "y": [1, 2]})
+
+
+def test_histogram_rejects_zero_bins(tmp_path):
+    fig = pv.Figure(plot=pv.Plot(series=[pv.series.Histogram([0.1, 0.4], bins=0)]))
+    with pytest.raises(ValueError, match="bins"):
+        fig.save_png(str(tmp_path / "histogram.png"))
+
+
+@pytest.mark.parametrize("values", [[float("nan"), float("inf")], "v"])
+def test_histogram_rejects_no_finite_value(tmp_path, values):
+    data_source = {"v": [float("nan"), None]}
+    with pytest.raises(ValueError, match="at least one finite value"):
+        render(tmp_path, [pv.series.Histogram(values)], data_source)
+
+
+def test_step_rejects_unknown_where(tmp_path):
+    fig = pv.Figure(plot=pv.Plot(series=[pv.series.Step([0, 1], [0, 1], where="after")]))
+    with pytest.raises(ValueError, match="Unknown step 'where' value"):
+        fig.save_png(str(tmp_path / "step.png"))
+
+
+def test_line_y_only_from_data_source(tmp_path):
+    png = render(tmp_path, [pv.series.Line("y")], data_source={"y": [2.0, 0.0, 1.0]})
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_line_y_keyword_only(tmp_path):
+    png = render(tmp_path, [pv.series.Line(y=[2.0, 0.0, 1.0])])
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_line_requires_values():
+    with pytest.raises(ValueError, match="Line requires y values"):
+        pv.series.Line()
+
+
+def test_data_source_records(tmp_path):
+    records = [{"x": 0, "y": 2}, {"x": 1, "y": 0}, {"x": 2, "y": 1}]
+    png = render(tmp_path, [pv.series.Scatter("x", "y")], data_source=records)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_data_source_2d_array(tmp_path):
+    array = np.array([[0.0, 2.0], [1.0, 0.0], [2.0, 1.0]])
+    png = render(tmp_path, [pv.series.Line("0", "1")], data_source=array)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_data_source_pandas_index(tmp_path):
+    pd = pytest.importorskip("pandas")
+    df = pd.DataFrame(
+        {"y": [1.0, 3.0, 2.0]},
+        index=pd.date_range("2024-01-01", periods=3, freq="D", name="date"),
+    )
+    png = render(tmp_path, [pv.series.Line("date", "y")], data_source=df)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_data_source_pandas_tz_aware(tmp_path):
+    pd = pytest.importorskip("pandas")
+    df = pd.DataFrame(
+        {
+            "date": pd.date_range("2024-03-30", periods=3, freq="D", tz="Europe/Paris"),
+            "y": [1.0, 3.0, 2.0],
+        }
+    )
+    png = render(tmp_path, [pv.series.Line("date", "y")], data_source=df)
+    assert png.startswith(PNG_SIGNATURE)
+    df = df.set_index("date")
+    png = render(tmp_path, [pv.series.Line("date", "y")], data_source=df)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_data_source_polars_nulls(tmp_path):
+    pl = pytest.importorskip("polars")
+    from datetime import datetime
+
+    df = pl.DataFrame(
+        {
+            "x": [0, 1, None, 3],
+            "y": [1.0, None, 2.0, 3.0],
+            "date": [datetime(2024, 1, d) for d in (1, 2, 3)] + [None],
+            "cat": ["a", None, "c", "d"],
+        }
+    )
+    png = render(tmp_path, [pv.series.Line("x", "y")], data_source=df)
+    assert png.startswith(PNG_SIGNATURE)
+    png = render(tmp_path, [pv.series.Line("date", "y")], data_source=df)
+    assert png.startswith(PNG_SIGNATURE)
+    png = render(tmp_path, [pv.series.HBar("x", "cat")], data_source=df)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_data_source_lists(tmp_path):
+    png = render(tmp_path, [pv.series.Line("0", "1")], data_source=[[0, 1, 2], [2, 0, 1]])
+    assert png.startswith(PNG_SIGNATURE)
+    png = render(tmp_path, [pv.series.Line("0")], data_source=[2.0, 0.0, 1.0])
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_data_source_lists_length_mismatch(tmp_path):
+    with pytest.raises(ValueError, match="same length"):
+        render(tmp_path, [pv.series.Line("0", "1")], data_source=[[0, 1, 2], [2, 0]])
+
+
+def test_save_svg(tmp_path):
+    fig = pv.Figure(size=(320, 240), plot=pv.Plot(series=[pv.series.Line([0, 1], [0, 1])]))
+    path = tmp_path / "line.svg"
+    fig.save_svg(str(path))
+    assert "<svg" in path.read_text()
+
+
+def test_png_bytes():
+    fig = pv.Figure(size=(320, 240), plot=pv.Plot(series=[pv.series.Line([0, 1, 2], [1, 0, 1])]))
+    assert fig.to_png_bytes().startswith(PNG_SIGNATURE)
+
+
+def test_rgba_array_shape():
+    fig = pv.Figure(size=(320, 240), plot=pv.Plot(series=[pv.series.Line([0, 1, 2], [1, 0, 1])]))
+    img = fig.to_rgba_array()
+    assert img.shape == (240, 320, 4)
+    assert img.dtype == np.uint8
+
+
+def test_save_subplots_png(tmp_path):
+    fig = pv.Figure(
+        size=(320, 240),
+        plots=[
+            pv.Plot(series=[pv.series.Line([0, 1], [0, 1])], subplot=(1, 1)),
+            pv.Plot(series=[pv.series.Line([0, 1], [1, 0])], subplot=(1, 2)),
+        ],
+    )
+    fig.save_subplots_png(str(tmp_path / "plot{}.png"))
+    assert (tmp_path / "plot1.png").read_bytes().startswith(PNG_SIGNATURE)
+    assert (tmp_path / "plot2.png").read_bytes().startswith(PNG_SIGNATURE)
+
+
+def test_save_subplots_png_requires_placeholder(tmp_path):
+    fig = pv.Figure(
+        plots=[
+            pv.Plot(series=[pv.series.Line([0, 1], [0, 1])], subplot=(1, 1)),
+            pv.Plot(series=[pv.series.Line([0, 1], [1, 0])], subplot=(1, 2)),
+        ],
+    )
+    with pytest.raises(ValueError):
+        fig.save_subplots_png(str(tmp_path / "plot.png"))
+
+
+def test_tick_fontsize(tmp_path):
+    series = [pv.series.Line([0, 1, 2], [1, 0, 1])]
+    png = render(tmp_path, series, y_axis=pv.Axis(tick_fontsize=14))
+    assert png.startswith(PNG_SIGNATURE)
+    with pytest.raises(ValueError, match="Tick font size must be positive"):
+        render(tmp_path, series, y_axis=pv.Axis(tick_fontsize=0))
+
+
+@pytest.mark.parametrize("grid", ["auto", "none", "red"])
+def test_grids(tmp_path, grid):
+    axis = pv.Axis(ticks="auto", grid=grid, minor_ticks="auto", minor_grid=grid)
+    png = render(tmp_path, [pv.series.Line([0, 1, 2], [1, 0, 1])], x_axis=axis)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_grid_rejects_invalid_dash_pattern(tmp_path):
+    axis = pv.Axis(ticks="auto", grid=pv.style.Stroke(color="red", pattern=[5, 0]))
+    with pytest.raises(ValueError, match="Dash pattern"):
+        render(tmp_path, [pv.series.Line([0, 1, 2], [1, 0, 1])], x_axis=axis)
+
+
+@pytest.mark.parametrize("offset", [0, 1, 4, 6, -1, 1e-9])
+def test_grid_dash_offset(tmp_path, offset):
+    grid = pv.style.Stroke(color="red", pattern=([4, 2, 1, 3], offset))
+    axis = pv.Axis(ticks="auto", grid=grid)
+    png = render(tmp_path, [pv.series.Line([0, 1, 2], [1, 0, 1])], x_axis=axis)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+@pytest.mark.parametrize(
+    "spines",
+    [["left", "right", "top", "bottom"], ["left", "bottom"], []],
+    ids=["all", "axes", "none"],
+)
+def test_spines(tmp_path, spines):
+    png = render(tmp_path, [pv.series.Line([0, 1, 2], [1, 0, 1])], spines=spines)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_spines_rejects_unknown_spine():
+    with pytest.raises(ValueError, match="Invalid spine"):
+        pv.Plot(series=[], spines=["front"])
+
+
+def test_spines_rejects_unsupported_set(tmp_path):
+    with pytest.raises(ValueError, match="Spines must be"):
+        render(tmp_path, [pv.series.Line([0, 1, 2], [1, 0, 1])], spines=["top"])
+
+
+def line_figure(**series_args):
+    line = pv.series.Line([0, 1, 2], [1, 0, 1], **series_args)
+    return pv.Figure(size=(320, 240), plot=pv.Plot(series=[line]))
+
+
+@pytest.mark.parametrize(
+    "colormap",
+    ["viridis", ["red", "blue"]],
+    ids=["named", "custom"],
+)
+def test_colormap_palette(colormap):
+    palette = {"colormap": colormap, "n": 4}
+    png = line_figure().to_png_bytes(style=pv.style.Style(palette=palette))
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_unknown_colormap():
+    palette = {"colormap": "no-such-colormap", "n": 4}
+    with pytest.raises(ValueError, match="Unknown colormap"):
+        line_figure().to_png_bytes(style=pv.style.Style(palette=palette))
+
+
+def test_palette_cycle_disabled():
+    series = [pv.series.Line([0, 1], [0, i]) for i in range(3)]
+    fig = pv.Figure(plot=pv.Plot(series=series), palette_cycle=False)
+    style = pv.style.Style(palette=["red", "blue"])
+    with pytest.raises(ValueError, match="palette_cycle is disabled"):
+        fig.to_png_bytes(style=style)
+
+
+def test_palette_cycle_disabled_explicit_color():
+    series = [pv.series.Line([0, 1], [0, i]) for i in range(2)]
+    series.append(pv.series.Line([0, 1], [1, 0], color="green"))
+    fig = pv.Figure(plot=pv.Plot(series=series), palette_cycle=False)
+    style = pv.style.Style(palette=["red", "blue"])
+    assert fig.to_png_bytes(style=style).startswith(PNG_SIGNATURE)
+
+
+def test_available_names():
+    assert pv.style.available_themes()
+    for name in pv.style.available_styles():
+        assert line_figure().to_png_bytes(style=name).startswith(PNG_SIGNATURE)
+    for name in pv.style.available_palettes():
+        style = pv.style.Style(palette=name)
+        assert line_figure().to_png_bytes(style=style).startswith(PNG_SIGNATURE)
+
+
+def test_title_style():
+    fig = pv.Figure(
+        size=(320, 240),
+        title="Title",
+        title_fontsize=24,
+        title_color="red",
+        plot=pv.Plot(series=[pv.series.Line([0, 1], [0, 1])]),
+    )
+    assert fig.to_png_bytes().startswith(PNG_SIGNATURE)
+
+
+@pytest.mark.parametrize(
+    "subplots, match",
+    [([(1, 1), (1, 1)], "both placed"), ([(0, 1), (1, 1)], "start at 1")],
+    ids=["shared", "zero"],
+)
+def test_invalid_subplots(subplots, match):
+    plots = [pv.Plot(series=[pv.series.Line([0, 1], [0, 1])], subplot=sp) for sp in subplots]
+    with pytest.raises(ValueError, match=match):
+        pv.Figure(plots=plots).to_png_bytes()
+
+
+def test_mismatched_inline_lengths():
+    fig = pv.Figure(plot=pv.Plot(series=[pv.series.Line([0, 1, 2], [1, 0])]))
+    with pytest.raises(ValueError, match="does not match the length"):
+        fig.validate()
+
+
+def test_missing_source_column():
+    fig = pv.Figure(plot=pv.Plot(series=[pv.series.Line("x", "z")]))
+    with pytest.raises(KeyError, match="Available columns: x, y"):
+        fig.validate(data_source={"x": [0, 1], "y": [1, 0]})
+
+
+def test_type_error_shows_received_value():
+    fig = pv.Figure(plot=pv.Plot(series=[pv.series.Histogram([0.1, 0.4], bins="ten")]))
+    with pytest.raises(TypeError, match="Got str: 'ten'"):
+        fig.validate()
+
+
+def test_save_png_returns_size(tmp_path):
+    fig = pv.Figure(size=(320, 240), plot=pv.Plot(series=[pv.series.Line([0, 1], [0, 1])]))
+    path = tmp_path / "line.png"
+    assert fig.save_png(str(path)) == (320, 240)
+    assert fig.save_png(str(path), scale=2) == (640, 480)
+    assert path.read_bytes().startswith(PNG_SIGNATURE)
+
+
+def test_line_decimation(tmp_path):
+    x = np.linspace(0, 1, 100_000)
+    png = render(tmp_path, [pv.series.Line(x, np.sin(50 * x), max_points=1000)])
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_line_decimation_rejects_small_max_points(tmp_path):
+    with pytest.raises(ValueError, match="max_points must be at least 4"):
+        render(tmp_path, [pv.series.Line([0, 1, 2], [1, 0, 1], max_points=2)])
+
+
+def test_column_cache():
+    pd = pytest.importorskip("pandas")
+    df = pd.DataFrame({"x": [0, 1, 2], "y": [2.0, 0.0, 1.0]})
+    fig = pv.Figure(size=(320, 240), plot=pv.Plot(series=[pv.series.Line("x", "y")]))
+    pv.set_column_cache()
+    try:
+        first = fig.to_rgba_array(data_source=df)
+        second = fig.to_rgba_array(data_source=df)
+        assert np.array_equal(first, second)
+        pv.clear_column_cache()
+    finally:
+        pv.set_column_cache(False)
+
+
+def test_data_source_lazy_column(tmp_path):
+    values = np.linspace(0, 1, 1000)
+    data_source = {"x": pv.LazyColumn(values, chunk_size=64), "y": values**2}
+    png = render(tmp_path, [pv.series.Line("x", "y")], data_source=data_source)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_data_source_lazy_column_error(tmp_path):
+    class Failing:
+        def __len__(self):
+            return 1000
+
+        def __getitem__(self, index):
+            raise OSError("cannot read chunk")
+
+    values = np.linspace(0, 1, 1000)
+    data_source = {"x": values, "y": pv.LazyColumn(Failing(), chunk_size=64)}
+    with pytest.raises(OSError, match="cannot read chunk"):
+        render(tmp_path, [pv.series.Line("x", "y")], data_source=data_source)
+
+
+@pytest.mark.parametrize("ext, signature", [("gif", b"GIF8"), ("png", PNG_SIGNATURE)])
+def test_save_animation(tmp_path, ext, signature):
+    frames = [
+        pv.Figure(size=(160, 120), plot=pv.Plot(series=[pv.series.Line([0, 1], [0, i])]))
+        for i in range(1, 4)
+    ]
+    path = tmp_path / f"anim.{ext}"
+    pv.save_animation(frames, str(path), fps=5)
+    assert path.read_bytes().startswith(signature)
+
+
+def test_save_animation_rejects_mixed_sizes(tmp_path):
+    frames = [
+        pv.Figure(size=size, plot=pv.Plot(series=[pv.series.Line([0, 1], [0, 1])]))
+        for size in [(160, 120), (320, 240)]
+    ]
+    with pytest.raises(ValueError):
+        pv.save_animation(frames, str(tmp_path / "anim.gif"))
+
+
+def test_renderer(tmp_path):
+    renderer = pv.Renderer(style="light", width=200, height=100)
+    fig = pv.Figure(plot=pv.Plot(series=[pv.series.Line([0, 1], [0, 1])]))
+    assert renderer.to_bytes(fig).startswith(PNG_SIGNATURE)
+    assert renderer.to_png_bytes(fig) == renderer.to_bytes(fig)
+    assert renderer.save_png(fig, str(tmp_path / "line.png")) == (200, 100)
+
+
+@pytest.mark.parametrize("margin", [0, 12.5, (0, 10)], ids=["zero", "even", "tuple"])
+def test_plot_margin(tmp_path, margin):
+    png = render(tmp_path, [pv.series.Line([0, 1, 2], [1, 0, 1])], margin=margin)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_plot_margin_rejects_negative(tmp_path):
+    with pytest.raises(ValueError, match="Plot margin must be non-negative"):
+        render(tmp_path, [pv.series.Line([0, 1, 2], [1, 0, 1])], margin=(5, -1))
+
+
+@pytest.mark.parametrize("stack_mode", ["none", "stacked", "percent"])
+def test_plot_stack_mode(tmp_path, stack_mode):
+    series = [
+        pv.series.HBar("low", "cat", name="low"),
+        pv.series.HBar([1, 2, 0], ["a", "b", "c"], name="high"),
+    ]
+    data_source = [{"low": 3, "cat": "a"}, {"low": 0, "cat": "b"}, {"low": 0, "cat": "c"}]
+    png = render(tmp_path, series, data_source, stack_mode=stack_mode)
+    assert png.startswith(PNG_SIGNATURE)
+
+
+def test_plot_stack_mode_rejects_unknown():
+    with pytest.raises(ValueError, match="Invalid stack mode"):
+        pv.Plot(series=[], stack_mode="overlap")
+
+
+def test_plot_stack_mode_rejects_mixed_series(tmp_path):
+    series = [
+        pv.series.HBar([3, 5], ["a", "b"]),
+        pv.series.Line([0, 1], [1, 0]),
+    ]
+    with pytest.raises(ValueError, match="Only HBar series can be stacked"):
+        render(tmp_path, series, stack_mode="stacked")
+
+
+def test_plot_stack_mode_rejects_different_categories(tmp_path):
+    series = [
+        pv.series.HBar([3, 5], ["a", "b"]),
+        pv.series.HBar([1, 2], ["b", "a"]),
+    ]
+    with pytest.raises(ValueError, match="same 'y' categories"):
+        render(tmp_path, series, stack_mode="percent")
+
+
+def test_plot_stack_mode_percent_rejects_negative(tmp_path):
+    series = [
+        pv.series.HBar([3, -5], ["a", "b"]),
+        pv.series.HBar([1, 2], ["a", "b"]),
+    ]
+    with pytest.raises(ValueError, match="non-negative"):
+        render(tmp_path, series, stack_mode="percent")
@@ -0,0 +1,48 @@
+import pytest
+
+import plotive as pv
+
+
+def line_figure(**series_args):
+    line = pv.series.Line([0, 1, 2], [1, 0, 1], **series_args)
+    return pv.Figure(size=(320, 240), plot=pv.Plot(series=[line]))
+
+
+@pytest.fixture
+def strict():
+    pv.set_strict()
+    yield
+    pv.set_strict(False)
+
+
+def test_strict_disabled_by_default():
+    assert not pv.is_strict()
+    fig = line_figure()
+    fig.plots[0].series[0].linewdith = 2
+    fig.validate()
+
+
+def test_strict_reports_unknown_attribute(strict):
+    assert pv.is_strict()
+    fig = line_figure()
+    fig.plots[0].series[0].linewdith = 2
+    with pytest.raises(AttributeError, match=r"Line\.linewdith"):
+        fig.validate()
+    with pytest.raises(AttributeError, match=r"Line\.linewdith"):
+        fig.to_png_bytes()
+
+
+def test_strict_accepts_known_attributes(strict):
+    fig = line_figure(linewidth=2)
+    fig.plots[0].series[0].linewidth = 3
+    fig.plots[0].x_axes[0].title = "x"
+    fig.validate()
+
+
+def test_strict_reports_names_only_read_by_constructor(strict):
+    fig = line_figure()
+    # Plot.__init__ uses Legend and isinstance, but does not store them on the plot
+    fig.plots[0].Legend = None
+    fig.plots[0].isinstance = None
+    with pytest.raises(AttributeError, match=r"Plot\.Legend, Plot\.isinstance"):
+        fig.validate()